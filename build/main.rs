@@ -5,13 +5,16 @@ use map::{AreaData, MapArea};
 use monster::{ExploreAbility, ExploreAction, Monster, MonsterType};
 use relic::Relic;
 
+#[cfg(feature = "gui")]
 mod atlas;
 mod sql;
 
 #[path = "../src/structs/map.rs"]
+#[allow(dead_code)]
 mod map;
 
 #[path = "../src/structs/monster.rs"]
+#[allow(dead_code)]
 mod monster;
 
 #[path = "../src/structs/relic.rs"]
@@ -20,6 +23,7 @@ mod relic;
 fn main() {
     println!("cargo::rerun-if-changed=./res/json/");
     println!("cargo::rerun-if-changed=./res/icons/");
+    println!("cargo::rerun-if-changed=./res/tables/");
 
     if cfg!(target_os = "windows") {
         let mut res = winresource::WindowsResource::new();
@@ -42,6 +46,15 @@ fn main() {
     to_rmp::<Relic>("./res/json/Relics.json");
     to_rmp::<u32>("./res/json/SwimmingMonsterList.json");
 
+    println!("cargo::rustc-env=GAME_DATA_HASH={:016x}", game_data_hash());
+
+    #[cfg(feature = "gui")]
+    build_gui();
+}
+
+/// Packs the icon atlases and compiles the Slint UI.
+#[cfg(feature = "gui")]
+fn build_gui() {
     atlas::create_atlas(
         "./res/icons/monsters/",
         "./res/out/atlas/monsters.png",
@@ -73,3 +86,20 @@ where
         fs::write(format!("./res/out/data/{}", output), &rmp).unwrap();
     }
 }
+
+/// Hashes the game data with FNV-1a, so that databases generated from other data can be detected.
+fn game_data_hash() -> u64 {
+    let mut paths = fs::read_dir("./res/out/data")
+        .unwrap()
+        .map(|x| x.unwrap().path())
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    paths
+        .iter()
+        .flat_map(|x| fs::read(x).unwrap())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
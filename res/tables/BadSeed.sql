@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS "BadSeed" (
+    "Seed" INTEGER NOT NULL,
+    "Randomizer" INTEGER NOT NULL,
+    "Tries" INTEGER NOT NULL,
+    "Draws" INTEGER NOT NULL,
+    PRIMARY KEY("Seed","Randomizer")
+)
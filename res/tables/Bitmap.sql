@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS "Bitmap" (
+    "Modes" TEXT NOT NULL,
+    "Value" INTEGER NOT NULL,
+    "Bits" BLOB NOT NULL,
+    PRIMARY KEY("Modes","Value")
+)
@@ -30,5 +30,6 @@ CREATE TABLE IF NOT EXISTS "Bravery" (
     "AbandonedTower" INTEGER,
     "BlobBurg" INTEGER,
     "ForgottenWorld" INTEGER,
+    "ShiftOffset" INTEGER,
     PRIMARY KEY("Id")
 )
\ No newline at end of file
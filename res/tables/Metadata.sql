@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS "Metadata" (
+    "Key" TEXT NOT NULL,
+    "Value" TEXT,
+    PRIMARY KEY("Key")
+)
\ No newline at end of file
@@ -13,5 +13,31 @@ CREATE TABLE IF NOT EXISTS "Relic" (
     "AbandonedTower" INTEGER,
     "BlobBurg" INTEGER,
     "ForgottenWorld" INTEGER,
+    "MountainPathScene" INTEGER,
+    "MountainPathChest" INTEGER,
+    "BlueCavesScene" INTEGER,
+    "BlueCavesChest" INTEGER,
+    "StrongholdDungeonScene" INTEGER,
+    "StrongholdDungeonChest" INTEGER,
+    "AncientWoodsScene" INTEGER,
+    "AncientWoodsChest" INTEGER,
+    "SnowyPeaksScene" INTEGER,
+    "SnowyPeaksChest" INTEGER,
+    "SunPalaceScene" INTEGER,
+    "SunPalaceChest" INTEGER,
+    "HorizonBeachScene" INTEGER,
+    "HorizonBeachChest" INTEGER,
+    "MagmaChamberScene" INTEGER,
+    "MagmaChamberChest" INTEGER,
+    "MysticalWorkshopScene" INTEGER,
+    "MysticalWorkshopChest" INTEGER,
+    "UnderworldScene" INTEGER,
+    "UnderworldChest" INTEGER,
+    "AbandonedTowerScene" INTEGER,
+    "AbandonedTowerChest" INTEGER,
+    "BlobBurgScene" INTEGER,
+    "BlobBurgChest" INTEGER,
+    "ForgottenWorldScene" INTEGER,
+    "ForgottenWorldChest" INTEGER,
     PRIMARY KEY("Id")
 )
\ No newline at end of file
@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use sanctuary_seeder::seed_finder::{db, enums::expression::Expression, score::SoftCondition};
+
+/// A seed finder and checker for Monster Sanctuary.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// The seeds database to use: a name in the data directory, or a path to a `.db` file, or a `.idx` file with the `columnar` feature.
+    ///
+    /// Defaults to `SANCTUARY_SEEDER_DB` if set, or `seeds`.
+    #[arg(short, long, global = true, value_name = "NAME|PATH")]
+    pub database: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Prints the monsters and relics generated for a seed.
+    Seed {
+        seed: u32,
+
+        #[command(flatten)]
+        modes: Modes,
+
+        /// Prints the game as JSON.
+        #[arg(long)]
+        json: bool,
+
+        /// Writes the game to a `.csv` or `.json` file instead of printing it.
+        #[arg(long, value_name = "FILE", conflicts_with = "json")]
+        export: Option<PathBuf>,
+
+        /// Writes every call to the random number generator to a file, as one JSON object
+        /// per line with its method, arguments, result and what it was drawn for.
+        #[arg(long, value_name = "FILE")]
+        trace: Option<PathBuf>,
+    },
+
+    /// Finds all seeds matching the given conditions in the seeds database, or without one.
+    Find {
+        #[command(flatten)]
+        modes: Modes,
+
+        /// A condition written as `<monster or relic>:<effect>[:<argument>]`.
+        ///
+        /// `any <monster type or explore ability>` matches a group of monsters instead,
+        /// e.g. `any Dragon:starter` or `any Ignite:army`, and `any monster` any egg's shift.
+        ///
+        /// Effects: available, chest, familiar, starter, swimming, bex, cryomancer,
+        /// cryomancer-required, end-of-time, army[:<shift>], area:<area>, egg:<area>[:<shift>],
+        /// scene:<scene>[:<chest>], replacement:<monster>. Shifts: normal, light, dark.
+        ///
+        /// Conditions can be combined with `&` (and), `|` (or), `!` (not) and parentheses,
+        /// e.g. `Vaero:starter | Kanko:starter`. Every condition given must match.
+        #[arg(short, long = "condition", value_name = "CONDITION", required = true)]
+        conditions: Vec<Expression>,
+
+        /// A condition seeds do not have to match, written as `[<weight>[*]=]<condition>`.
+        ///
+        /// Each seed matching it scores its weight, 1 by default, e.g. `3=Vaero:starter`
+        /// or `-1=any Dragon:army`. With `*`, an `any` condition or conditions joined with `|`
+        /// score the weight for each one matched, e.g. `2*=any Dragon:army` for each Dragon
+        /// in the army. Seeds are then ranked from the highest score, and printed
+        /// with the numbers of the soft conditions they match, or their indexes in JSON.
+        #[arg(
+            short = 'p',
+            long = "prefer",
+            value_name = "CONDITION",
+            allow_hyphen_values = true
+        )]
+        soft_conditions: Vec<SoftCondition>,
+
+        /// Prints the seeds as JSON.
+        #[arg(long)]
+        json: bool,
+
+        /// Only prints the number of seeds found.
+        #[arg(long, conflicts_with_all = ["json", "offset", "limit"])]
+        count: bool,
+
+        /// Skips this many seeds before printing the others.
+        #[arg(long, default_value_t = 0)]
+        offset: u32,
+
+        /// Prints at most this many seeds.
+        ///
+        /// Without soft conditions to rank seeds by, searching without a database
+        /// stops as soon as enough seeds are found.
+        #[arg(long)]
+        limit: Option<u32>,
+
+        /// Writes the seeds to a `.csv` or `.json` file instead of printing them,
+        /// along with the familiar, starters and other monsters of note in Bravery,
+        /// the replacements in Randomizer and the relic of each area in Relics of Chaos.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["json", "count"])]
+        export: Option<PathBuf>,
+
+        /// Generates the seeds to search instead of reading the seeds database.
+        ///
+        /// This needs no database, but takes about as long as generating one.
+        /// Seeds are printed as they are found.
+        #[arg(long)]
+        no_database: bool,
+
+        /// The first seed to search without a database.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.start(), requires = "no_database")]
+        first: u32,
+
+        /// The last seed to search without a database.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.end(), requires = "no_database")]
+        last: u32,
+    },
+
+    /// Generates the seeds database.
+    ///
+    /// A database holding other seeds is replaced.
+    Generate {
+        /// The first seed to generate.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.start())]
+        first: u32,
+
+        /// The last seed to generate.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.end())]
+        last: u32,
+    },
+
+    /// Generates seeds again and compares them to the seeds database.
+    ///
+    /// Prints every game that differs, along with its game modes and row.
+    Verify {
+        /// Only checks this many seeds, evenly spread over the seeds of the database.
+        #[arg(long, value_name = "COUNT")]
+        sample: Option<u32>,
+    },
+
+    /// Scans seeds for bad seeds, and stores them in the seeds database.
+    ///
+    /// Bad seeds have no Bravery eggs meeting the exploration requirements,
+    /// and freeze the game when starting a new save in Bravery modes.
+    BadSeeds {
+        /// The first seed to scan.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.start())]
+        first: u32,
+
+        /// The last seed to scan.
+        #[arg(long, default_value_t = *db::DEFAULT_SEEDS.end())]
+        last: u32,
+
+        /// Only prints the bad seeds already stored, without scanning again.
+        #[arg(long)]
+        stored: bool,
+
+        /// Prints the bad seeds as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Lists the seeds databases in the data directory.
+    Databases,
+}
+
+/// The game modes to generate seeds with.
+#[derive(Args)]
+pub struct Modes {
+    /// Enables the Randomizer mode.
+    #[arg(short, long)]
+    pub randomizer: bool,
+
+    /// Enables the Bravery mode.
+    #[arg(short, long)]
+    pub bravery: bool,
+
+    /// Enables the Relics of Chaos mode.
+    #[arg(short = 'l', long)]
+    pub relic: bool,
+}
@@ -0,0 +1,534 @@
+use std::{
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use clap::Parser;
+
+use sanctuary_seeder::{
+    seed_finder::{
+        backend::{self, Backend, Error, Page},
+        bad_seeds, db,
+        enums::expression::Expression,
+        export::{self, Format},
+        score::{self, ScoredSeed, SoftCondition},
+        search, verify,
+    },
+    structs::game_manager,
+};
+
+use args::{Cli, Command, Modes};
+
+mod args;
+mod output;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let database = cli
+        .database
+        .as_deref()
+        .map_or_else(db::default_path, db::database_path);
+
+    let res = match cli.command {
+        Command::Seed {
+            seed,
+            modes,
+            json,
+            export,
+            trace,
+        } => seed_command(seed, modes, json, export, trace),
+        Command::Find {
+            modes,
+            conditions,
+            soft_conditions,
+            json,
+            count,
+            offset,
+            limit,
+            export,
+            no_database,
+            first,
+            last,
+        } => {
+            let output = Output {
+                json,
+                count,
+                page: (offset > 0 || limit.is_some()).then_some(Page {
+                    offset,
+                    limit: limit.unwrap_or(u32::MAX),
+                }),
+                export,
+            };
+
+            if let Some(Err(err)) = output.export.as_deref().map(Format::from_path) {
+                Err(err.to_string())
+            } else if !modes.randomizer && !modes.bravery {
+                Err(String::from(
+                    "Please select at least the Randomizer or Bravery game modes.",
+                ))
+            } else if let Some(err) = conditions
+                .iter()
+                .chain(soft_conditions.iter().map(|x| &x.expression))
+                .find_map(|x| x.validate(modes.randomizer, modes.bravery, modes.relic))
+            {
+                Err(format!("Invalid condition: {err}"))
+            } else if first > last {
+                Err(String::from(
+                    "The first seed must not be greater than the last one.",
+                ))
+            } else if no_database {
+                search_command(first..=last, modes, conditions, &soft_conditions, output)
+            } else {
+                find_command(&database, modes, conditions, &soft_conditions, output)
+            }
+        }
+        Command::Generate { first, last } => generate_command(&database, first, last),
+        Command::Verify { sample } => verify_command(&database, sample),
+        Command::BadSeeds {
+            first,
+            last,
+            stored,
+            json,
+        } => bad_seeds_command(&database, first..=last, stored, json),
+        Command::Databases => databases_command(),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints the game generated for a seed, or writes it to a file.
+fn seed_command(
+    seed: u32,
+    modes: Modes,
+    json: bool,
+    export: Option<PathBuf>,
+    trace: Option<PathBuf>,
+) -> Result<(), String> {
+    if !modes.randomizer && !modes.bravery && !modes.relic {
+        return Err(String::from("Please select at least one game mode."));
+    }
+
+    // Failing with the selected modes is already an error
+    for bad_seed in bad_seeds::check(seed) {
+        if !modes.bravery || bad_seed.is_randomizer != modes.randomizer {
+            eprintln!(
+                "WARNING: Seed {seed} freezes the game in {} modes: {}. Do not use it with these modes.",
+                bad_seed.modes(),
+                bad_seed.error()
+            );
+        }
+    }
+
+    let game = match trace {
+        // Also written if the game fails to generate
+        Some(path) => {
+            let (game, calls) = game_manager::generate_game_traced(
+                seed,
+                modes.randomizer,
+                modes.bravery,
+                modes.relic,
+            );
+
+            export::export_trace(&path, &calls).map_err(|x| x.to_string())?;
+            eprintln!("Traced {} call(s) to {}.", calls.len(), path.display());
+
+            game
+        }
+        None => game_manager::generate_game(seed, modes.randomizer, modes.bravery, modes.relic),
+    }
+    .map_err(|x| format!("Seed {seed} is invalid in these game modes: {x}. The game will fail to generate this seed, and it cannot be used."))?;
+
+    if let Some(path) = export {
+        Format::from_path(&path)
+            .and_then(|format| export::export_game(&path, format, &game))
+            .map_err(|x| x.to_string())?;
+
+        eprintln!("Exported seed {seed} to {}.", path.display());
+    } else if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&game).map_err(|x| x.to_string())?
+        );
+    } else {
+        output::print_game(&game);
+    }
+
+    Ok(())
+}
+
+/// How to print the seeds found.
+struct Output {
+    json: bool,
+    /// Only prints the number of seeds.
+    count: bool,
+    page: Option<Page>,
+    /// Writes the seeds to this file instead of printing them.
+    export: Option<PathBuf>,
+}
+
+/// Prints all seeds matching the conditions.
+fn find_command(
+    database: &Path,
+    modes: Modes,
+    conditions: Vec<Expression>,
+    soft_conditions: &[SoftCondition],
+    output: Output,
+) -> Result<(), String> {
+    let backend = open_database(database)?;
+
+    if !backend.is_complete().map_err(|x| x.to_string())? {
+        return Err(String::from(
+            "The seeds database is incomplete. Resume its creation with the `generate` command.",
+        ));
+    }
+
+    let conditions = Arc::new(Mutex::new(conditions));
+
+    if output.count {
+        let count = backend
+            .count_seeds(modes.randomizer, modes.bravery, modes.relic, conditions)
+            .map_err(|x| x.to_string())?;
+
+        println!("{count}");
+        return Ok(());
+    }
+
+    let seeds = score::find_seeds(
+        backend.as_ref(),
+        modes.randomizer,
+        modes.bravery,
+        modes.relic,
+        conditions,
+        soft_conditions,
+        output.page,
+    )
+    .map_err(|x| x.to_string())?;
+
+    match output.export {
+        Some(path) => export_seeds(&path, &seeds, modes, Some(backend.as_ref())),
+        None => print_seeds(&seeds, soft_conditions, output.json),
+    }
+}
+
+/// Prints all seeds of a range matching the conditions, generating them instead of using the database.
+///
+/// Without soft conditions to rank them by, seeds are printed as they are found,
+/// and the search stops once the last seed of the page is found.
+fn search_command(
+    seeds: RangeInclusive<u32>,
+    modes: Modes,
+    conditions: Vec<Expression>,
+    soft_conditions: &[SoftCondition],
+    output: Output,
+) -> Result<(), String> {
+    let is_ranked = !soft_conditions.is_empty();
+    let stream = !output.json && !output.count && output.export.is_none() && !is_ranked;
+    let page = output.page.unwrap_or(Page {
+        offset: 0,
+        limit: u32::MAX,
+    });
+    let end = page.offset as usize + page.limit as usize;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut all_seeds = vec![];
+
+    let res = search::search(
+        seeds,
+        modes.randomizer,
+        modes.bravery,
+        modes.relic,
+        Arc::new(Mutex::new(conditions)),
+        soft_conditions,
+        |_, _| {},
+        |seeds| {
+            for seed in seeds {
+                if stream && (page.offset as usize..end).contains(&all_seeds.len()) {
+                    println!("{}", seed.seed);
+                }
+
+                all_seeds.push(seed.clone());
+            }
+
+            if !is_ranked && !output.count && all_seeds.len() >= end {
+                stop.store(true, Ordering::Relaxed);
+            }
+        },
+        stop.clone(),
+    );
+
+    match res {
+        // Stopped once the page was found
+        Ok(()) | Err(Error::Stopped) => {}
+        Err(err) => return Err(err.to_string()),
+    }
+
+    if output.count {
+        println!("{}", all_seeds.len());
+        return Ok(());
+    }
+
+    if stream {
+        eprintln!("Found {} seed(s).", page.slice(&all_seeds).len());
+        return Ok(());
+    }
+
+    score::rank(&mut all_seeds);
+
+    match output.export {
+        Some(path) => export_seeds(&path, page.slice(&all_seeds), modes, None),
+        None => print_seeds(page.slice(&all_seeds), soft_conditions, output.json),
+    }
+}
+
+/// Writes the seeds found to a file, reading their games from the database if any.
+fn export_seeds(
+    path: &Path,
+    seeds: &[ScoredSeed],
+    modes: Modes,
+    backend: Option<&dyn Backend>,
+) -> Result<(), String> {
+    Format::from_path(path)
+        .and_then(|format| {
+            export::export_seeds(
+                path,
+                format,
+                seeds,
+                modes.randomizer,
+                modes.bravery,
+                modes.relic,
+                backend,
+            )
+        })
+        .map_err(|x| x.to_string())?;
+
+    eprintln!("Exported {} seed(s) to {}.", seeds.len(), path.display());
+    Ok(())
+}
+
+/// Prints the seeds found, with their score and the numbers of the soft conditions they match if any.
+///
+/// As JSON, seeds are only numbers without soft conditions.
+fn print_seeds(
+    seeds: &[ScoredSeed],
+    soft_conditions: &[SoftCondition],
+    json: bool,
+) -> Result<(), String> {
+    if json {
+        let json = if soft_conditions.is_empty() {
+            serde_json::to_string(&seeds.iter().map(|x| x.seed).collect::<Vec<u32>>())
+        } else {
+            serde_json::to_string(seeds)
+        };
+
+        println!("{}", json.map_err(|x| x.to_string())?);
+        return Ok(());
+    }
+
+    for seed in seeds {
+        if soft_conditions.is_empty() {
+            println!("{}", seed.seed);
+        } else {
+            println!("{}", output::format_scored_seed(seed));
+        }
+    }
+
+    eprintln!("Found {} seed(s).", seeds.len());
+    Ok(())
+}
+
+/// Generates seeds again and prints the games that differ from the database.
+fn verify_command(database: &Path, sample: Option<u32>) -> Result<(), String> {
+    let backend = open_database(database)?;
+
+    let seeds = backend
+        .seed_range()
+        .map_err(|x| x.to_string())?
+        .ok_or("Could not read the seeds of the database.")?;
+
+    let count = *seeds.end() as u64 - *seeds.start() as u64 + 1;
+    // Rounded up, so that no more than `sample` seeds are checked
+    let step = sample.map_or(1, |x| {
+        count.div_ceil(x.max(1) as u64).min(u32::MAX as u64) as u32
+    });
+
+    let res = verify::verify(
+        backend.as_ref(),
+        seeds,
+        step,
+        |done, total| {
+            eprint!("\rVerifying database... {}%", done * 100 / total);
+            io::stderr().flush().ok();
+        },
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    eprintln!();
+
+    let mismatches = res.map_err(|x| x.to_string())?;
+
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+
+    if !mismatches.is_empty() {
+        return Err(format!(
+            "{} game(s) of the database differ from the generator. Generate it again with the `generate` command.",
+            mismatches.len()
+        ));
+    }
+
+    eprintln!("Every game checked matches the generator.");
+
+    Ok(())
+}
+
+/// Scans a range of seeds for bad seeds and stores them in the database, then prints them.
+fn bad_seeds_command(
+    database: &Path,
+    seeds: RangeInclusive<u32>,
+    stored: bool,
+    json: bool,
+) -> Result<(), String> {
+    if seeds.start() > seeds.end() {
+        return Err(String::from(
+            "The first seed must not be greater than the last one.",
+        ));
+    }
+
+    let res = if stored {
+        bad_seeds::stored(database, seeds)
+    } else {
+        let res = bad_seeds::scan(
+            database,
+            seeds,
+            |done, total| {
+                eprint!("\rScanning seeds... {}%", done as u64 * 100 / total as u64);
+                io::stderr().flush().ok();
+            },
+            Arc::new(AtomicBool::new(false)),
+        );
+
+        eprintln!();
+        res
+    };
+
+    let bad_seeds = res.map_err(|x| x.to_string())?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&bad_seeds).map_err(|x| x.to_string())?
+        );
+        return Ok(());
+    }
+
+    for bad_seed in &bad_seeds {
+        println!("{bad_seed}");
+    }
+
+    eprintln!("Found {} bad seed(s).", bad_seeds.len());
+    Ok(())
+}
+
+/// Opens a database, or returns an error if it does not exist or was created by another version.
+fn open_database(database: &Path) -> Result<Box<dyn Backend>, String> {
+    if !database.is_file() {
+        return Err(format!(
+            "Could not find the seeds database at \"{}\". Create it with the `generate` command first.",
+            database.display()
+        ));
+    }
+
+    let backend = backend::open(database).map_err(|x| x.to_string())?;
+
+    if let Some(reason) = backend.outdated_reason().map_err(|x| x.to_string())? {
+        return Err(format!(
+            "The seeds database is outdated: {reason}. Generate it again with the `generate` command."
+        ));
+    }
+
+    Ok(backend)
+}
+
+/// Generates the seeds database, reporting progress on stderr.
+fn generate_command(database: &Path, first: u32, last: u32) -> Result<(), String> {
+    if first > last {
+        return Err(String::from(
+            "The first seed must not be greater than the last one.",
+        ));
+    }
+
+    let backend = backend::open(database).map_err(|x| x.to_string())?;
+
+    let res = backend.generate(
+        first..=last,
+        &|done, total| {
+            eprint!("\rGenerating database... {}%", done * 100 / total);
+            io::stderr().flush().ok();
+        },
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    eprintln!();
+
+    if let Err(err) = res {
+        return Err(format!(
+            "{err}\nRun `generate` again to resume from where it stopped."
+        ));
+    }
+
+    eprintln!(
+        "Database generated with success at \"{}\"!",
+        database.display()
+    );
+
+    Ok(())
+}
+
+/// Prints the name, path and seeds of every database in the data directory.
+fn databases_command() -> Result<(), String> {
+    for name in db::databases() {
+        let path = db::database_path(&name);
+        let status = database_status(&path).map_err(|x| x.to_string())?;
+
+        println!("{name}: {} ({status})", path.display());
+    }
+
+    Ok(())
+}
+
+/// Returns whether a database can be used, and which seeds it holds.
+fn database_status(path: &Path) -> backend::Result<String> {
+    if !path.is_file() {
+        return Ok(String::from("not generated"));
+    }
+
+    let backend = match backend::open(path) {
+        Ok(backend) => backend,
+        Err(err) => return Ok(format!("unsupported, {err}")),
+    };
+
+    if let Some(reason) = backend.outdated_reason()? {
+        return Ok(format!("outdated, {reason}"));
+    }
+
+    Ok(match backend.seed_range()? {
+        Some(seeds) if backend.is_complete()? => {
+            format!("seeds {} to {}", seeds.start(), seeds.end())
+        }
+        Some(seeds) => format!("seeds {} to {}, incomplete", seeds.start(), seeds.end()),
+        None => String::from("unknown seeds"),
+    })
+}
@@ -0,0 +1,169 @@
+use sanctuary_seeder::{
+    data::GAME_DATA,
+    seed_finder::score::ScoredSeed,
+    structs::{
+        game::Game,
+        modes::{
+            bravery::{BraveryData, Shift},
+            relic::RelicData,
+        },
+    },
+};
+
+/// Prints a human-readable description of a game.
+pub fn print_game(game: &Game) {
+    let modes = [
+        (game.is_randomizer, "Randomizer"),
+        (game.is_bravery, "Bravery"),
+        (game.is_relic, "Relics of Chaos"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, name)| *name)
+    .collect::<Vec<&str>>();
+
+    println!("Seed {} ({})", game.seed, modes.join(" + "));
+
+    if let Some(mapping) = &game.mapping {
+        print_mapping(mapping);
+    }
+
+    if let Some(bravery) = &game.bravery_data {
+        print_bravery(bravery);
+    }
+
+    if let Some(relics) = &game.relics {
+        print_relics(relics);
+    }
+}
+
+/// Returns a seed with its score and the numbers of the soft conditions it matches, from 1.
+pub fn format_scored_seed(seed: &ScoredSeed) -> String {
+    let matched = seed
+        .matched
+        .iter()
+        .map(|x| (x + 1).to_string())
+        .collect::<Vec<String>>();
+
+    format!(
+        "{}\tscore {}\tmatches {}",
+        seed.seed,
+        seed.score,
+        if matched.is_empty() {
+            String::from("none")
+        } else {
+            matched.join(", ")
+        }
+    )
+}
+
+fn print_mapping(mapping: &[Option<u32>]) {
+    println!();
+    println!("Randomizer");
+
+    for (monster, replacement) in mapping.iter().enumerate() {
+        if let Some(replacement) = replacement {
+            println!(
+                "  {} => {}",
+                monster_name(monster as u32),
+                monster_name(*replacement)
+            );
+        }
+    }
+}
+
+fn print_bravery(bravery: &BraveryData) {
+    println!();
+    println!("Bravery");
+    println!("  Familiar: {}", monster_name(bravery.familiar));
+    println!(
+        "  Starters: {}",
+        bravery.starters[1..]
+            .iter()
+            .map(|x| monster_name(*x))
+            .collect::<Vec<&str>>()
+            .join(", ")
+    );
+    println!("  Sun Palace: {}", monster_name(bravery.swimming));
+    println!("  Bex: {}", monster_name(bravery.bex));
+
+    if let Some(cryomancer) = bravery.cryomancer {
+        println!(
+            "  Cryomancer: gives {}, wants {}",
+            monster_name(cryomancer),
+            monster_name(bravery.cryomancer_required)
+        );
+    }
+
+    println!(
+        "  Eternity's End: {}",
+        bravery
+            .end_of_time
+            .iter()
+            .map(|x| monster_name(*x))
+            .collect::<Vec<&str>>()
+            .join(", ")
+    );
+
+    println!("  Monster Army:");
+
+    for (monster, shift) in bravery.army.iter().zip(bravery.get_army_eggs_shift()) {
+        if let Some(monster) = monster {
+            println!("    {}{}", monster_name(*monster), shift_name(shift));
+        }
+    }
+
+    println!("  Area eggs:");
+
+    for ((area, egg), shift) in GAME_DATA
+        .areas
+        .iter()
+        .zip(&bravery.eggs)
+        .zip(bravery.get_area_eggs_shift())
+    {
+        println!(
+            "    {}: {}{}",
+            area.name,
+            monster_name(*egg),
+            shift_name(shift)
+        );
+    }
+}
+
+fn print_relics(relics: &RelicData) {
+    println!();
+    println!("Relics of Chaos");
+
+    for ((area, relic), (scene, chest)) in GAME_DATA
+        .areas
+        .iter()
+        .zip(&relics.list)
+        .zip(&relics.area_chests)
+    {
+        println!(
+            "  {}: {} ({scene}, chest {chest})",
+            area.name,
+            relic_name(*relic)
+        );
+    }
+}
+
+fn monster_name(id: u32) -> &'static str {
+    &GAME_DATA.monsters[id as usize].name
+}
+
+fn relic_name(id: u32) -> &'static str {
+    GAME_DATA
+        .relics
+        .iter()
+        .find(|x| x.id == id)
+        .map_or("", |x| &x.name)
+}
+
+fn shift_name(shift: Shift) -> &'static str {
+    match shift {
+        Shift::Normal => "",
+        Shift::Light => " (Light)",
+        Shift::Dark => " (Dark)",
+    }
+}
@@ -1,10 +1,16 @@
 use std::sync::LazyLock;
 
-use crate::{structs::game_data::GameData, ui::display::Display};
+use crate::structs::game_data::GameData;
+#[cfg(feature = "gui")]
+use crate::ui::display::Display;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub static GAME_DATA: LazyLock<GameData> = LazyLock::new(|| GameData::new());
+/// Hash of the game data the generator was built with.
+pub const GAME_DATA_HASH: &str = env!("GAME_DATA_HASH");
+
+pub static GAME_DATA: LazyLock<GameData> = LazyLock::new(GameData::new);
+#[cfg(feature = "gui")]
 pub static DISPLAY: LazyLock<Display> = LazyLock::new(|| Display::new(&GAME_DATA));
 
 pub mod macros {
@@ -1,4 +1,5 @@
 pub mod data;
 pub mod seed_finder;
 pub mod structs;
+#[cfg(feature = "gui")]
 pub mod ui;
@@ -1,13 +1,18 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::sync::{Arc, Mutex};
+use std::sync::{atomic::AtomicBool, Arc, Mutex};
 
 use slint::{ComponentHandle, Image, ModelRc, SharedString, StandardListViewItem, VecModel};
 
 use sanctuary_seeder::{
     data::{DISPLAY, VERSION},
+    seed_finder::{db, enums::expression::Expression, score::SoftCondition},
     structs::game::Game,
-    ui::{enums::condition::Condition, request, types::AppWindow},
+    ui::{
+        finder::{self, FoundSeeds},
+        request,
+        types::AppWindow,
+    },
 };
 
 fn main() -> Result<(), slint::PlatformError> {
@@ -16,24 +21,53 @@ fn main() -> Result<(), slint::PlatformError> {
 
     let game: Arc<Mutex<Option<Game>>> = Arc::new(Mutex::new(None));
     let is_max_seed = Arc::new(Mutex::new(false));
-    let conditions: Arc<Mutex<Vec<Condition>>> = Arc::new(Mutex::new(vec![]));
+    let conditions: Arc<Mutex<Vec<Expression>>> = Arc::new(Mutex::new(vec![]));
+    let soft_conditions: Arc<Mutex<Vec<SoftCondition>>> = Arc::new(Mutex::new(vec![]));
+    let found_seeds: Arc<Mutex<Option<FoundSeeds>>> = Arc::new(Mutex::new(None));
+    let stop_find = Arc::new(AtomicBool::new(false));
 
     // Initialize icons and models
     init(&ui);
 
     // Seed finder's game modes toggled on/off
-    ui.on_req_find_modes(request::find_modes(ui_weak.clone(), conditions.clone()));
+    ui.on_req_find_modes(request::find_modes(
+        ui_weak.clone(),
+        conditions.clone(),
+        soft_conditions.clone(),
+    ));
 
     // Find seed
-    ui.on_req_find(request::find(ui_weak.clone(), conditions.clone()));
+    ui.on_req_find(request::find(
+        ui_weak.clone(),
+        conditions.clone(),
+        soft_conditions.clone(),
+        found_seeds.clone(),
+        stop_find.clone(),
+    ));
+
+    // More seeds found requested
+    ui.on_req_load_seeds(request::load_seeds(ui_weak.clone(), found_seeds.clone()));
+
+    // Seeds found exported
+    ui.on_req_export_seeds(request::export_seeds(ui_weak.clone(), found_seeds.clone()));
+
+    // Search without database stopped
+    ui.on_req_stop_find(request::stop_find(stop_find.clone()));
 
     // Condition added
     ui.on_req_add_condition(request::add_condition(ui_weak.clone(), conditions.clone()));
 
-    // Condition removed
+    // Soft condition added
+    ui.on_req_add_soft_condition(request::add_soft_condition(
+        ui_weak.clone(),
+        soft_conditions.clone(),
+    ));
+
+    // Condition or soft condition removed
     ui.on_req_remove_condition(request::remove_condition(
         ui_weak.clone(),
         conditions.clone(),
+        soft_conditions.clone(),
     ));
 
     // Seed table row clicked
@@ -42,6 +76,9 @@ fn main() -> Result<(), slint::PlatformError> {
     // Area selected
     ui.on_req_area(request::area(ui_weak.clone(), game.clone()));
 
+    // Seed exported
+    ui.on_req_export_game(request::export_game(ui_weak.clone(), game.clone()));
+
     // Seed or game mode changed
     ui.on_req_seed(request::seed(
         ui_weak.clone(),
@@ -56,19 +93,13 @@ fn init(ui: &AppWindow) {
     ui.set_version(SharedString::from(VERSION));
 
     // Initialize images
-    ui.set_champion_icon(Image::from_rgba8(
-        DISPLAY.get_icon(&String::from("champion")),
-    ));
+    ui.set_champion_icon(Image::from_rgba8(DISPLAY.get_icon("champion")));
 
-    ui.set_egg_icon(Image::from_rgba8(DISPLAY.get_icon(&String::from("egg"))));
+    ui.set_egg_icon(Image::from_rgba8(DISPLAY.get_icon("egg")));
 
-    ui.set_egg_light_icon(Image::from_rgba8(
-        DISPLAY.get_icon(&String::from("egg_light")),
-    ));
+    ui.set_egg_light_icon(Image::from_rgba8(DISPLAY.get_icon("egg_light")));
 
-    ui.set_egg_dark_icon(Image::from_rgba8(
-        DISPLAY.get_icon(&String::from("egg_dark")),
-    ));
+    ui.set_egg_dark_icon(Image::from_rgba8(DISPLAY.get_icon("egg_dark")));
 
     // Initialize models
     ui.set_values(ModelRc::new(VecModel::from(vec![
@@ -77,6 +108,9 @@ fn init(ui: &AppWindow) {
     ui.set_effects(ModelRc::new(VecModel::from(vec![
         StandardListViewItem::from(SharedString::from("")),
     ])));
+    ui.set_databases(finder::init_databases());
+    ui.set_first_seed(SharedString::from(db::DEFAULT_SEEDS.start().to_string()));
+    ui.set_last_seed(SharedString::from(db::DEFAULT_SEEDS.end().to_string()));
     ui.set_conditions_display(ModelRc::new(VecModel::from(vec![])));
     ui.set_found_seeds(ModelRc::new(VecModel::from(vec![])));
 }
@@ -1 +1,11 @@
+pub mod backend;
+pub mod bad_seeds;
+pub mod bitmap;
+#[cfg(feature = "columnar")]
+pub mod columnar;
 pub mod db;
+pub mod enums;
+pub mod export;
+pub mod score;
+pub mod search;
+pub mod verify;
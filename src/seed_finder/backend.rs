@@ -0,0 +1,284 @@
+use std::{
+    fmt, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+
+#[cfg(feature = "columnar")]
+use crate::seed_finder::columnar::ColumnarBackend;
+use crate::seed_finder::{db, enums::expression::Expression};
+
+/// Extension of the databases stored in the columnar format instead of SQLite.
+pub const COLUMNAR_EXTENSION: &str = "idx";
+
+#[derive(Debug)]
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    Io(io::Error),
+    /// The database cannot be read or used.
+    Invalid(String),
+    /// The operation was cancelled.
+    Stopped,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Sqlite(err) => write!(f, "{err}"),
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Invalid(err) => write!(f, "{err}"),
+            Error::Stopped => write!(f, "The operation was cancelled."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::Sqlite(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A page of the seeds found: at most `limit` seeds after the first `offset` ones.
+#[derive(Clone, Copy)]
+pub struct Page {
+    pub offset: u32,
+    pub limit: u32,
+}
+
+impl Page {
+    /// Returns the items of a list within this page.
+    pub fn slice<'a, T>(&self, list: &'a [T]) -> &'a [T] {
+        let start = (self.offset as usize).min(list.len());
+        let end = start.saturating_add(self.limit as usize).min(list.len());
+
+        &list[start..end]
+    }
+}
+
+/// A storage of the generated seeds, which the seed finder queries.
+///
+/// Every backend holds the same rows as the SQLite tables, and finds the same seeds.
+pub trait Backend: Send + Sync {
+    fn path(&self) -> &Path;
+
+    /// Generates a range of seeds and stores them, resuming a stopped generation of the same seeds.
+    ///
+    /// `progress` is called with the number of batches done and the total number of batches.
+    /// Setting `stop` cancels the generation with [`Error::Stopped`].
+    fn generate(
+        &self,
+        seeds: RangeInclusive<u32>,
+        progress: &dyn Fn(u32, u32),
+        stop: Arc<AtomicBool>,
+    ) -> Result<()>;
+
+    /// Returns why the database cannot be used by this version of the generator, if it cannot.
+    fn outdated_reason(&self) -> Result<Option<String>>;
+
+    /// Returns true if the database holds every seed of its range.
+    fn is_complete(&self) -> Result<bool>;
+
+    /// Returns the range of seeds the database holds, or is being generated with,
+    /// or `None` if it could not be read.
+    fn seed_range(&self) -> Result<Option<RangeInclusive<u32>>>;
+
+    fn find_seeds(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+    ) -> Result<Vec<u32>>;
+
+    /// Finds the seeds matching the conditions within a page of them, in order.
+    ///
+    /// By default, every seed is found before keeping those of the page.
+    fn find_seeds_page(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+        page: Page,
+    ) -> Result<Vec<u32>> {
+        let seeds = self.find_seeds(is_randomizer, is_bravery, is_relic, conditions)?;
+        Ok(page.slice(&seeds).to_vec())
+    }
+
+    /// Returns the number of seeds matching the conditions.
+    fn count_seeds(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+    ) -> Result<u64> {
+        let seeds = self.find_seeds(is_randomizer, is_bravery, is_relic, conditions)?;
+        Ok(seeds.len() as u64)
+    }
+
+    /// Returns a reader of the stored rows, to use on a single thread.
+    fn reader(&self) -> Result<Box<dyn RowReader + '_>>;
+}
+
+/// Reads the rows of a database as they would be stored in the SQLite tables.
+pub trait RowReader {
+    /// Returns the values of a row after its id, or `None` if there is no such row.
+    fn read(&mut self, table: &str, id: i64) -> Result<Option<Vec<Option<i64>>>>;
+}
+
+/// Returns the backend of a database, depending on the extension of its file.
+pub fn open(path: &Path) -> Result<Box<dyn Backend>> {
+    if path.extension().is_some_and(|x| x == COLUMNAR_EXTENSION) {
+        #[cfg(feature = "columnar")]
+        return Ok(Box::new(ColumnarBackend::new(path)));
+
+        #[cfg(not(feature = "columnar"))]
+        return Err(Error::Invalid(String::from(
+            "Columnar databases require the `columnar` feature.",
+        )));
+    }
+
+    Ok(Box::new(SqliteBackend::new(path)))
+}
+
+pub struct SqliteBackend {
+    path: PathBuf,
+}
+
+impl SqliteBackend {
+    pub fn new(path: &Path) -> SqliteBackend {
+        SqliteBackend {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn generate(
+        &self,
+        seeds: RangeInclusive<u32>,
+        progress: &dyn Fn(u32, u32),
+        stop: Arc<AtomicBool>,
+    ) -> Result<()> {
+        let stopped = stop.clone();
+
+        db::generate(&self.path, seeds, progress, stop).map_err(|err| {
+            if stopped.load(Ordering::Relaxed) {
+                Error::Stopped
+            } else {
+                Error::Sqlite(err)
+            }
+        })
+    }
+
+    fn outdated_reason(&self) -> Result<Option<String>> {
+        Ok(db::outdated_reason(&self.path)?)
+    }
+
+    fn is_complete(&self) -> Result<bool> {
+        Ok(db::is_complete(&self.path)?)
+    }
+
+    fn seed_range(&self) -> Result<Option<RangeInclusive<u32>>> {
+        Ok(db::seed_range(&self.path)?)
+    }
+
+    fn find_seeds(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+    ) -> Result<Vec<u32>> {
+        Ok(db::find_seeds(
+            &self.path,
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            conditions,
+        )?)
+    }
+
+    fn find_seeds_page(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+        page: Page,
+    ) -> Result<Vec<u32>> {
+        Ok(db::find_seeds_page(
+            &self.path,
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            conditions,
+            Some(page),
+        )?)
+    }
+
+    fn count_seeds(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+    ) -> Result<u64> {
+        Ok(db::count_seeds(
+            &self.path,
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            conditions,
+        )?)
+    }
+
+    fn reader(&self) -> Result<Box<dyn RowReader + '_>> {
+        Ok(Box::new(SqliteReader {
+            conn: Connection::open(&self.path)?,
+        }))
+    }
+}
+
+struct SqliteReader {
+    conn: Connection,
+}
+
+impl RowReader for SqliteReader {
+    fn read(&mut self, table: &str, id: i64) -> Result<Option<Vec<Option<i64>>>> {
+        // Table names only ever come from the known tables
+        let mut stmt = self
+            .conn
+            .prepare_cached(&format!("SELECT * FROM {table} WHERE Id = ?"))?;
+        let count = stmt.column_count();
+
+        Ok(stmt
+            .query_row(params_from_iter([id]), |row| {
+                (1..count)
+                    .map(|i| row.get::<usize, Option<i64>>(i))
+                    .collect::<rusqlite::Result<Vec<Option<i64>>>>()
+            })
+            .optional()?)
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    path::Path,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::{
+    seed_finder::{
+        backend::{Error, Result, COLUMNAR_EXTENSION},
+        db::{self, BATCH_SIZE},
+    },
+    structs::{error::GenerationError, game_manager},
+};
+
+/// A seed for which no Bravery eggs can be found, which freezes the game when starting a new save.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BadSeed {
+    pub seed: u32,
+    /// True if the seed fails with the Randomizer and Bravery modes, false with Bravery alone.
+    pub is_randomizer: bool,
+    pub tries: u32,
+    pub draws: u64,
+}
+
+impl BadSeed {
+    /// Returns the game modes this seed fails with.
+    pub fn modes(&self) -> &'static str {
+        if self.is_randomizer {
+            "Randomizer + Bravery"
+        } else {
+            "Bravery"
+        }
+    }
+
+    pub fn error(&self) -> GenerationError {
+        GenerationError::BraveryEggs {
+            tries: self.tries,
+            draws: self.draws,
+        }
+    }
+}
+
+impl fmt::Display for BadSeed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.seed, self.modes(), self.error())
+    }
+}
+
+/// Returns the game modes with Bravery a seed fails with, by generating its Bravery monsters.
+///
+/// Relics do not change whether a seed fails, as they are generated afterwards.
+pub fn check(seed: u32) -> Vec<BadSeed> {
+    [false, true]
+        .into_iter()
+        .filter_map(|is_randomizer| {
+            match game_manager::generate_game(seed, is_randomizer, true, false) {
+                Err(GenerationError::BraveryEggs { tries, draws }) => Some(BadSeed {
+                    seed,
+                    is_randomizer,
+                    tries,
+                    draws,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Checks every seed of a range and stores the bad ones in the database, replacing those
+/// stored for this range before. Returns the bad seeds found, in order.
+///
+/// The database has to be generated first, so that bad seeds are stored along with the versions
+/// they were found with, and other tables are left as is.
+///
+/// `progress` is called with the number of batches done and the total number of batches.
+/// Setting `stop` cancels the scan with [`Error::Stopped`], keeping the batches already stored.
+pub fn scan(
+    path: &Path,
+    seeds: RangeInclusive<u32>,
+    progress: impl Fn(u32, u32),
+    stop: Arc<AtomicBool>,
+) -> Result<Vec<BadSeed>> {
+    check_sqlite(path)?;
+
+    if !path.is_file() {
+        return Err(Error::Invalid(String::from(
+            "The database does not exist. Generate it first with the `generate` command.",
+        )));
+    }
+
+    let mut conn = Connection::open(path)?;
+
+    if let Some(reason) = db::outdated(&conn)? {
+        return Err(Error::Invalid(format!(
+            "The database is outdated: {reason}. Generate it again with the `generate` command."
+        )));
+    }
+
+    let mut found = vec![];
+
+    db::generate_batches(
+        &seeds,
+        0,
+        check,
+        progress,
+        &stop,
+        || Error::Stopped,
+        |batch, bad_seeds| {
+            let first = *seeds.start() as u64 + batch as u64 * BATCH_SIZE as u64;
+            let last = (first + BATCH_SIZE as u64 - 1).min(*seeds.end() as u64);
+
+            let tx = conn.transaction()?;
+            tx.execute(
+                "DELETE FROM BadSeed WHERE Seed BETWEEN ? AND ?",
+                params![first, last],
+            )?;
+
+            let mut stmt = tx.prepare("INSERT INTO BadSeed VALUES (?, ?, ?, ?)")?;
+
+            for bad_seed in bad_seeds.iter().flatten() {
+                stmt.execute(params![
+                    bad_seed.seed,
+                    bad_seed.is_randomizer,
+                    bad_seed.tries,
+                    bad_seed.draws
+                ])?;
+
+                found.push(bad_seed.clone());
+            }
+
+            stmt.finalize()?;
+            Ok(tx.commit()?)
+        },
+    )?;
+
+    Ok(found)
+}
+
+/// Returns the bad seeds of a range stored in the database, in order.
+///
+/// Seeds that were never scanned are not known to be bad, even if they are.
+pub fn stored(path: &Path, seeds: RangeInclusive<u32>) -> Result<Vec<BadSeed>> {
+    check_sqlite(path)?;
+
+    if !path.is_file() {
+        return Ok(vec![]);
+    }
+
+    let conn = Connection::open(path)?;
+
+    if !db::has_table(&conn, "BadSeed")? {
+        return Ok(vec![]);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT Seed, Randomizer, Tries, Draws FROM BadSeed WHERE Seed BETWEEN ? AND ? ORDER BY Seed, Randomizer",
+    )?;
+
+    let bad_seeds = stmt
+        .query_map(params![seeds.start(), seeds.end()], |row| {
+            Ok(BadSeed {
+                seed: row.get(0)?,
+                is_randomizer: row.get(1)?,
+                tries: row.get(2)?,
+                draws: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<BadSeed>>>()?;
+
+    Ok(bad_seeds)
+}
+
+/// Returns an error for columnar databases, which do not store bad seeds.
+fn check_sqlite(path: &Path) -> Result<()> {
+    if path.extension().is_some_and(|x| x == COLUMNAR_EXTENSION) {
+        return Err(Error::Invalid(String::from(
+            "Bad seeds can only be stored in SQLite databases.",
+        )));
+    }
+
+    Ok(())
+}
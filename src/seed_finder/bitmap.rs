@@ -0,0 +1,333 @@
+use std::{collections::HashMap, ops::RangeInclusive, path::Path};
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+use crate::{
+    data::macros::relic,
+    seed_finder::{
+        db,
+        enums::{condition::Condition, expression::Expression},
+    },
+};
+
+/// Games of the `Bravery` table, by `Id % 2`.
+pub const BRAVERY_GAMES: [&str; 2] = ["Bravery", "Randomizer + Bravery"];
+
+/// Games of the `Relic` table, by `Id % 3`.
+pub const RELIC_GAMES: [&str; 3] = [
+    "Randomizer + Relics",
+    "Bravery + Relics",
+    "Randomizer + Bravery + Relics",
+];
+
+/// Value of the bitmap holding the seeds a game is stored for, as bad seeds are not.
+const STORED: i64 = -1;
+
+/// Most seeds a database is given bitmaps for, as every bitmap is held in memory while they
+/// are built: 512 KiB each, rather than 512 MiB each for every seed.
+///
+/// Databases of more seeds are searched from their rows only.
+pub const MAX_SEEDS: u64 = 1 << 22;
+
+/// A set of seeds within the range of a database, as one bit per seed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitmap {
+    seeds: RangeInclusive<u32>,
+    words: Vec<u64>,
+}
+
+impl Bitmap {
+    pub fn empty(seeds: RangeInclusive<u32>) -> Bitmap {
+        let words = vec![0; (db::seed_count(&seeds) as usize).div_ceil(64)];
+
+        Bitmap { seeds, words }
+    }
+
+    pub fn full(seeds: RangeInclusive<u32>) -> Bitmap {
+        let mut bitmap = Bitmap::empty(seeds);
+        bitmap.invert();
+        bitmap
+    }
+
+    fn from_bytes(seeds: RangeInclusive<u32>, bytes: &[u8]) -> Bitmap {
+        let words = bytes
+            .chunks_exact(8)
+            .map(|x| u64::from_le_bytes(x.try_into().unwrap()))
+            .collect();
+
+        Bitmap { seeds, words }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.words.iter().flat_map(|x| x.to_le_bytes()).collect()
+    }
+
+    pub fn insert(&mut self, seed: u32) {
+        let index = (seed - self.seeds.start()) as usize;
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn contains(&self, seed: u32) -> bool {
+        if !self.seeds.contains(&seed) {
+            return false;
+        }
+
+        let index = (seed - self.seeds.start()) as usize;
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Returns the number of seeds in the set.
+    pub fn count(&self) -> u64 {
+        self.words.iter().map(|x| x.count_ones() as u64).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|x| *x == 0)
+    }
+
+    /// Keeps only the seeds that are also in `other`.
+    pub fn intersect(&mut self, other: &Bitmap) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    /// Adds the seeds of `other`.
+    pub fn union(&mut self, other: &Bitmap) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Replaces the set with every other seed of its range.
+    pub fn invert(&mut self) {
+        for word in &mut self.words {
+            *word = !*word;
+        }
+
+        // Bits past the last seed are never set
+        let len = db::seed_count(&self.seeds) as usize;
+
+        if let Some(last) = self.words.last_mut().filter(|_| !len.is_multiple_of(64)) {
+            *last &= (1 << (len % 64)) - 1;
+        }
+    }
+
+    /// Returns the seeds of the set, in order.
+    pub fn seeds(&self) -> impl Iterator<Item = u32> + '_ {
+        let first = *self.seeds.start() as u64;
+
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            (0..64)
+                .filter(move |x| word & (1 << x) != 0)
+                .map(move |x| (first + i as u64 * 64 + x) as u32)
+        })
+    }
+}
+
+/// The bitmaps of a database, giving the seeds each monster or relic is available in
+/// for every game mode combination, to find seeds without reading every row.
+///
+/// Bitmaps are read from the database as conditions need them, then kept.
+pub struct BitmapIndex {
+    conn: Connection,
+    seeds: RangeInclusive<u32>,
+    bitmaps: HashMap<(&'static str, i64), Bitmap>,
+}
+
+impl BitmapIndex {
+    /// Opens the bitmaps of a database, or returns `None` if they were not built.
+    pub fn open(path: &Path) -> Result<Option<BitmapIndex>> {
+        let conn = Connection::open(path)?;
+
+        let Some(seeds) = db::seed_range(path)? else {
+            return Ok(None);
+        };
+
+        if !has_bitmaps(&conn)? {
+            return Ok(None);
+        }
+
+        Ok(Some(BitmapIndex {
+            conn,
+            seeds,
+            bitmaps: HashMap::new(),
+        }))
+    }
+
+    /// Returns the range of seeds of the database.
+    pub fn seed_range(&self) -> &RangeInclusive<u32> {
+        &self.seeds
+    }
+
+    /// Returns the seeds for which a monster or relic ID is available in a game.
+    pub fn get(&mut self, modes: &'static str, value: i64) -> Result<&Bitmap> {
+        if !self.bitmaps.contains_key(&(modes, value)) {
+            let bytes = self
+                .conn
+                .query_row(
+                    "SELECT Bits FROM Bitmap WHERE Modes = ? AND Value = ?",
+                    params![modes, value],
+                    |row| row.get::<usize, Vec<u8>>(0),
+                )
+                .optional()?;
+
+            // Monsters and relics that are never available have no bitmap
+            let bitmap = match bytes {
+                Some(bytes) => Bitmap::from_bytes(self.seeds.clone(), &bytes),
+                None => Bitmap::empty(self.seeds.clone()),
+            };
+
+            self.bitmaps.insert((modes, value), bitmap);
+        }
+
+        Ok(&self.bitmaps[&(modes, value)])
+    }
+
+    /// Returns the seeds for which all the selected games are stored.
+    pub fn stored(
+        &mut self,
+        bravery: Option<&'static str>,
+        relic: Option<&'static str>,
+    ) -> Result<Bitmap> {
+        let mut seeds = Bitmap::full(self.seeds.clone());
+
+        for modes in bravery.into_iter().chain(relic) {
+            seeds.intersect(self.get(modes, STORED)?);
+        }
+
+        Ok(seeds)
+    }
+
+    /// Returns the seeds matching an expression in the selected games,
+    /// or `None` if some of its conditions have no bitmap.
+    ///
+    /// Seeds whose games are not stored may be included, see [`BitmapIndex::stored`].
+    pub fn evaluate(
+        &mut self,
+        expression: &Expression,
+        bravery: Option<&'static str>,
+        relic: Option<&'static str>,
+    ) -> Result<Option<Bitmap>> {
+        let bitmap = match expression {
+            Expression::Condition(condition) => {
+                let (modes, value) = match (condition, bravery, relic) {
+                    (Condition::MonsterAvailable(id), Some(modes), _) => (modes, *id as i64),
+                    (Condition::RelicAvailable(relic), _, Some(modes)) => {
+                        (modes, relic!(*relic).id as i64)
+                    }
+                    _ => return Ok(None),
+                };
+
+                self.get(modes, value)?.clone()
+            }
+            Expression::And(list) => {
+                let mut seeds = Bitmap::full(self.seeds.clone());
+
+                for expression in list {
+                    let Some(bitmap) = self.evaluate(expression, bravery, relic)? else {
+                        return Ok(None);
+                    };
+
+                    seeds.intersect(&bitmap);
+                }
+
+                seeds
+            }
+            Expression::Any(_, list) | Expression::Or(list) => {
+                let mut seeds = Bitmap::empty(self.seeds.clone());
+
+                for expression in list {
+                    let Some(bitmap) = self.evaluate(expression, bravery, relic)? else {
+                        return Ok(None);
+                    };
+
+                    seeds.union(&bitmap);
+                }
+
+                seeds
+            }
+            Expression::Not(expression) => {
+                let Some(mut seeds) = self.evaluate(expression, bravery, relic)? else {
+                    return Ok(None);
+                };
+
+                seeds.invert();
+                seeds
+            }
+        };
+
+        Ok(Some(bitmap))
+    }
+}
+
+/// Builds the bitmaps of a complete database, replacing any previous ones,
+/// unless it holds more than `MAX_SEEDS` seeds.
+///
+/// Monsters are available in a Bravery game if they are in any column but the shift offset,
+/// and relics in a Relic game if they are in any area.
+pub(crate) fn build(conn: &mut Connection, seeds: &RangeInclusive<u32>) -> Result<()> {
+    if db::seed_count(seeds) > MAX_SEEDS {
+        return Ok(());
+    }
+
+    let mut bitmaps = HashMap::<(&str, i64), Bitmap>::new();
+
+    for (table, games, columns) in [
+        ("Bravery", &BRAVERY_GAMES[..], 30),
+        ("Relic", &RELIC_GAMES[..], 13),
+    ] {
+        // Table names only ever come from the known tables
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {table}"))?;
+        let mut rows = stmt.query([])?;
+
+        while let Some(row) = rows.next()? {
+            let id = row.get::<usize, i64>(0)?;
+            let modes = games[(id % games.len() as i64) as usize];
+            let seed = (id / games.len() as i64) as u32;
+
+            let mut insert = |value| {
+                bitmaps
+                    .entry((modes, value))
+                    .or_insert_with(|| Bitmap::empty(seeds.clone()))
+                    .insert(seed)
+            };
+
+            insert(STORED);
+
+            for column in 1..=columns {
+                if let Some(value) = row.get::<usize, Option<i64>>(column)? {
+                    insert(value);
+                }
+            }
+        }
+    }
+
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM Bitmap", ())?;
+
+    {
+        let mut stmt = tx.prepare("INSERT INTO Bitmap VALUES (?, ?, ?)")?;
+
+        for ((modes, value), bitmap) in &bitmaps {
+            stmt.execute(params![modes, value, bitmap.to_bytes()])?;
+        }
+    }
+
+    tx.commit()
+}
+
+/// Returns true if the bitmaps of the database were built.
+pub(crate) fn has_bitmaps(conn: &Connection) -> Result<bool> {
+    let has_table = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'Bitmap'",
+        [],
+        |row| row.get::<usize, u32>(0),
+    )? > 0;
+
+    Ok(has_table
+        && conn
+            .query_row("SELECT 1 FROM Bitmap LIMIT 1", [], |_| Ok(()))
+            .optional()?
+            .is_some())
+}
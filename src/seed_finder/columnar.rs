@@ -0,0 +1,575 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Read,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc, Mutex},
+    thread,
+};
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::{
+    data::{macros::relic, GAME_DATA, GAME_DATA_HASH, VERSION},
+    seed_finder::{
+        backend::{Backend, Error, Result, RowReader},
+        db::{self, Row, SeedGames, BATCH_SIZE},
+        enums::{condition::Condition, expression::Expression},
+    },
+    structs::modes::bravery::BraveryData,
+};
+
+/// Identifies the files of this format.
+const MAGIC: &[u8; 8] = b"SSEEDIDX";
+
+/// Version of the format, to increment whenever its layout changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// Size of the header, followed by the columns.
+const HEADER_SIZE: usize = 128;
+
+/// Stored for `NULL`, and in every column of a game that fails to generate.
+const NULL: u8 = u8::MAX;
+
+/// First column of the Randomizer game, which holds the replacement of each monster.
+const RANDOMIZER: usize = 0;
+/// First column of the Bravery, then Randomizer + Bravery games.
+const BRAVERY: [usize; 2] = [106, 138];
+/// First column of the Randomizer + Relics, Bravery + Relics,
+/// then Randomizer + Bravery + Relics games.
+const RELIC: [usize; 3] = [170, 209, 248];
+const COLUMNS: usize = 287;
+
+// Columns of a Bravery game, in the order of the `Bravery` table
+const ARMY: usize = 10;
+const AREA_EGGS: usize = 17;
+/// The shift offset goes over a byte, and is stored in two columns, the low byte first.
+const SHIFT_OFFSET: usize = 30;
+
+/// Columns of a Relic game holding the scene and chest of each area's relic.
+const RELIC_CHESTS: usize = 13;
+
+/// A database storing each column of each game as one byte per seed,
+/// which is memory-mapped to find seeds without SQLite.
+///
+/// Monsters, scenes and chests are stored by ID, relics by their index in the game data.
+/// A game that fails to generate has `NULL` in its first column.
+///
+/// The file starts with a header of `HEADER_SIZE` bytes:
+/// - `0..8`: `MAGIC`
+/// - `8..12`: `FORMAT_VERSION`
+/// - `12..16` and `16..20`: the first and last seed
+/// - `20..24`: the number of batches committed
+/// - `24..40`: the hash of the game data
+/// - `40..72`: the generator version, padded with zeros
+pub struct ColumnarBackend {
+    path: PathBuf,
+}
+
+struct Header {
+    format: u32,
+    seeds: RangeInclusive<u32>,
+    batches: u32,
+    game_data: String,
+    generator: String,
+}
+
+impl Header {
+    fn new(seeds: RangeInclusive<u32>, batches: u32) -> Header {
+        Header {
+            format: FORMAT_VERSION,
+            seeds,
+            batches,
+            game_data: String::from(GAME_DATA_HASH),
+            generator: String::from(VERSION),
+        }
+    }
+
+    /// Returns the header of a file, or why it is not a database of this format.
+    fn read(file: &mut File) -> std::result::Result<Header, String> {
+        let mut bytes = [0; HEADER_SIZE];
+
+        if file.read_exact(&mut bytes).is_err() || &bytes[0..8] != MAGIC {
+            return Err(String::from("it is not a columnar seeds database"));
+        }
+
+        let int = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let text = |range: RangeInclusive<usize>| {
+            String::from_utf8_lossy(&bytes[range])
+                .trim_end_matches('\0')
+                .to_string()
+        };
+
+        Ok(Header {
+            format: int(8),
+            seeds: int(12)..=int(16),
+            batches: int(20),
+            game_data: text(24..=39),
+            generator: text(40..=71),
+        })
+    }
+
+    fn write(&self, bytes: &mut [u8]) {
+        bytes[..HEADER_SIZE].fill(0);
+        bytes[0..8].copy_from_slice(MAGIC);
+        bytes[8..12].copy_from_slice(&self.format.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.seeds.start().to_le_bytes());
+        bytes[16..20].copy_from_slice(&self.seeds.end().to_le_bytes());
+        bytes[20..24].copy_from_slice(&self.batches.to_le_bytes());
+
+        let game_data = &self.game_data.as_bytes()[..self.game_data.len().min(16)];
+        let generator = &self.generator.as_bytes()[..self.generator.len().min(32)];
+
+        bytes[24..24 + game_data.len()].copy_from_slice(game_data);
+        bytes[40..40 + generator.len()].copy_from_slice(generator);
+    }
+
+    /// Returns why a database with this header cannot be used by this version, if it cannot.
+    fn outdated_reason(&self) -> Option<String> {
+        if self.format != FORMAT_VERSION {
+            Some(format!(
+                "its format version is {} instead of {FORMAT_VERSION}",
+                self.format
+            ))
+        } else if self.generator != VERSION {
+            Some(format!(
+                "it was created by version {} of the generator instead of {VERSION}",
+                self.generator
+            ))
+        } else if self.game_data != GAME_DATA_HASH {
+            Some(String::from("it was created from other game data"))
+        } else {
+            None
+        }
+    }
+
+    fn seed_count(&self) -> usize {
+        db::seed_count(&self.seeds) as usize
+    }
+}
+
+impl ColumnarBackend {
+    pub fn new(path: &Path) -> ColumnarBackend {
+        ColumnarBackend {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Maps the database to read it, if it can be used by this version.
+    fn map(&self) -> Result<(Header, Mmap)> {
+        let mut file = File::open(&self.path)?;
+        let header = Header::read(&mut file).map_err(Error::Invalid)?;
+
+        if let Some(reason) = header.outdated_reason() {
+            return Err(Error::Invalid(format!(
+                "The seeds database is outdated: {reason}."
+            )));
+        }
+
+        // SAFETY: the database is only modified while it is being generated,
+        // and it is not searched until it is complete
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() != HEADER_SIZE + COLUMNS * header.seed_count() {
+            return Err(Error::Invalid(String::from(
+                "The seeds database is truncated.",
+            )));
+        }
+
+        Ok((header, map))
+    }
+}
+
+impl Backend for ColumnarBackend {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn generate(
+        &self,
+        seeds: RangeInclusive<u32>,
+        progress: &dyn Fn(u32, u32),
+        stop: Arc<AtomicBool>,
+    ) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+
+        let count = db::seed_count(&seeds) as usize;
+        let size = (HEADER_SIZE + COLUMNS * count) as u64;
+
+        // Resume a database of the same version and seeds, or create it again
+        let start = match Header::read(&mut file) {
+            Ok(header)
+                if header.outdated_reason().is_none()
+                    && header.seeds == seeds
+                    && file.metadata()?.len() == size =>
+            {
+                header.batches
+            }
+            _ => {
+                file.set_len(0)?;
+                file.set_len(size)?;
+                0
+            }
+        };
+
+        // SAFETY: the file is only modified through this mapping until the generation ends
+        let mut map = unsafe { MmapMut::map_mut(&file)? };
+        Header::new(seeds.clone(), start).write(&mut map);
+
+        db::generate_batches(
+            &seeds,
+            start,
+            SeedGames::new,
+            progress,
+            &stop,
+            || Error::Stopped,
+            |batch, games| {
+                let columns = &mut map[HEADER_SIZE..];
+
+                for (i, games) in games.iter().enumerate() {
+                    let index = batch as usize * BATCH_SIZE as usize + i;
+
+                    for row in games.rows() {
+                        write_row(columns, count, index, &row);
+                    }
+                }
+
+                // Commit the batch only once its seeds are written
+                map.flush()?;
+                Header::new(seeds.clone(), batch + 1).write(&mut map);
+                map.flush_range(0, HEADER_SIZE)?;
+
+                Ok(())
+            },
+        )
+    }
+
+    fn outdated_reason(&self) -> Result<Option<String>> {
+        match Header::read(&mut File::open(&self.path)?) {
+            Ok(header) => Ok(header.outdated_reason()),
+            Err(reason) => Ok(Some(reason)),
+        }
+    }
+
+    fn is_complete(&self) -> Result<bool> {
+        let mut file = File::open(&self.path)?;
+
+        let Ok(header) = Header::read(&mut file) else {
+            return Ok(false);
+        };
+
+        let size = (HEADER_SIZE + COLUMNS * header.seed_count()) as u64;
+
+        Ok(header.outdated_reason().is_none()
+            && file.metadata()?.len() == size
+            && header.batches == db::batch_count(&header.seeds))
+    }
+
+    fn seed_range(&self) -> Result<Option<RangeInclusive<u32>>> {
+        Ok(Header::read(&mut File::open(&self.path)?)
+            .ok()
+            .map(|x| x.seeds))
+    }
+
+    fn find_seeds(
+        &self,
+        is_randomizer: bool,
+        is_bravery: bool,
+        is_relic: bool,
+        conditions: Arc<Mutex<Vec<Expression>>>,
+    ) -> Result<Vec<u32>> {
+        let (header, map) = self.map()?;
+        let count = header.seed_count();
+        let columns = &map[HEADER_SIZE..];
+
+        // Same games as the joins of the SQL query
+        let bravery = is_bravery.then_some(BRAVERY[is_randomizer as usize]);
+        let relic = is_relic.then_some(match (is_randomizer, is_bravery) {
+            (true, true) => RELIC[2],
+            (false, true) => RELIC[1],
+            _ => RELIC[0],
+        });
+
+        let conditions = Expression::And(conditions.lock().unwrap().clone());
+        let mut checks = vec![compile(&conditions, bravery, relic)];
+
+        // Games that fail to generate are not stored
+        checks.extend(bravery.map(Check::NotNull));
+        checks.extend(relic.map(Check::NotNull));
+
+        let check = Check::All(checks);
+        let first = *header.seeds.start() as u64;
+
+        let workers = thread::available_parallelism().map_or(1, |x| x.get());
+        let chunk = count.div_ceil(workers).max(1);
+
+        Ok(thread::scope(|scope| {
+            let handles = (0..count)
+                .step_by(chunk)
+                .map(|start| {
+                    let check = &check;
+
+                    scope.spawn(move || {
+                        (start..(start + chunk).min(count))
+                            .filter(|x| check.matches(columns, count, *x))
+                            .map(|x| (first + x as u64) as u32)
+                            .collect::<Vec<u32>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|x| x.join().unwrap())
+                .collect()
+        }))
+    }
+
+    fn reader(&self) -> Result<Box<dyn RowReader + '_>> {
+        let (header, map) = self.map()?;
+
+        Ok(Box::new(ColumnarReader { header, map }))
+    }
+}
+
+/// Returns the first column of a row's game, and the number of values of the row.
+fn row_columns(table: &str, id: i64) -> (usize, usize) {
+    match table {
+        "Randomizer" => (RANDOMIZER, BRAVERY[0] - RANDOMIZER),
+        "Bravery" => (BRAVERY[id as usize % 2], SHIFT_OFFSET + 1),
+        _ => (RELIC[id as usize % 3], COLUMNS - RELIC[2]),
+    }
+}
+
+/// Returns the number of rows of a table per seed.
+fn rows_per_seed(table: &str) -> i64 {
+    match table {
+        "Randomizer" => 1,
+        "Bravery" => 2,
+        _ => 3,
+    }
+}
+
+fn write_row(columns: &mut [u8], count: usize, index: usize, row: &Row) {
+    let (first, width) = row_columns(row.table, row.id);
+
+    for column in 0..width {
+        let value = row.values.as_ref().and_then(|x| x[column]);
+
+        if row.table == "Bravery" && column == SHIFT_OFFSET {
+            let [low, high] = value.map_or([NULL; 2], |x| (x as u16).to_le_bytes());
+
+            columns[(first + column) * count + index] = low;
+            columns[(first + column + 1) * count + index] = high;
+        } else {
+            columns[(first + column) * count + index] = encode(row.table, column, value);
+        }
+    }
+}
+
+/// Returns the byte stored for the value of a column.
+fn encode(table: &str, column: usize, value: Option<i64>) -> u8 {
+    let Some(value) = value else {
+        return NULL;
+    };
+
+    let value = match table {
+        "Relic" if column < RELIC_CHESTS => GAME_DATA
+            .relics
+            .iter()
+            .position(|x| x.id as i64 == value)
+            .unwrap() as i64,
+        _ => value,
+    };
+
+    // Every ID and index of the game data is below `NULL`
+    u8::try_from(value).ok().filter(|x| *x != NULL).unwrap()
+}
+
+/// Returns the value of a column from its stored byte.
+fn decode(table: &str, column: usize, byte: u8) -> Option<i64> {
+    match (table, byte) {
+        (_, NULL) => None,
+        ("Relic", _) if column < RELIC_CHESTS => Some(relic!(byte).id as i64),
+        _ => Some(byte as i64),
+    }
+}
+
+struct ColumnarReader {
+    header: Header,
+    map: Mmap,
+}
+
+impl RowReader for ColumnarReader {
+    fn read(&mut self, table: &str, id: i64) -> Result<Option<Vec<Option<i64>>>> {
+        let count = self.header.seed_count();
+        let index = id / rows_per_seed(table) - *self.header.seeds.start() as i64;
+
+        if index < 0 || index as usize >= count {
+            return Ok(None);
+        }
+
+        let columns = &self.map[HEADER_SIZE..];
+        let (first, width) = row_columns(table, id);
+
+        let get = |column: usize| columns[column * count + index as usize];
+
+        let values = (0..width)
+            .map(|x| match (table, x) {
+                ("Bravery", SHIFT_OFFSET) => {
+                    Some(u16::from_le_bytes([get(first + x), get(first + x + 1)]) as i64)
+                }
+                _ => decode(table, x, get(first + x)),
+            })
+            .collect::<Vec<Option<i64>>>();
+
+        // The first column of a row is never `NULL`, unless its game is not stored
+        Ok(values[0].map(|_| values))
+    }
+}
+
+/// A condition compiled to the columns it reads.
+enum Check {
+    /// The value is in any of the columns.
+    In(u8, Vec<usize>),
+    NotNull(usize),
+    /// The egg at this position of the area or army eggs has a shift,
+    /// from the shift offset column and the positions of the shift.
+    Shift(usize, u32, Vec<u32>),
+    All(Vec<Check>),
+    Any(Vec<Check>),
+    Not(Box<Check>),
+}
+
+impl Check {
+    fn matches(&self, columns: &[u8], count: usize, index: usize) -> bool {
+        let get = |column: usize| columns[column * count + index];
+
+        match self {
+            Check::In(value, list) => list.iter().any(|x| get(*x) == *value),
+            Check::NotNull(column) => get(*column) != NULL,
+            Check::Shift(column, position, positions) => {
+                let offset = u16::from_le_bytes([get(*column), get(column + 1)]) as u32;
+
+                positions.contains(&((offset + position) % BraveryData::SHIFT_DATA.len() as u32))
+            }
+            Check::All(list) => list.iter().all(|x| x.matches(columns, count, index)),
+            Check::Any(list) => list.iter().any(|x| x.matches(columns, count, index)),
+            Check::Not(check) => !check.matches(columns, count, index),
+        }
+    }
+}
+
+/// Compiles an expression for the games of the selected modes, with the same result as its SQL.
+fn compile(expression: &Expression, bravery: Option<usize>, relic: Option<usize>) -> Check {
+    match expression {
+        Expression::Condition(condition) => {
+            compile_condition(condition, bravery, relic).unwrap_or(Check::Any(vec![]))
+        }
+        Expression::And(list) => {
+            Check::All(list.iter().map(|x| compile(x, bravery, relic)).collect())
+        }
+        Expression::Any(_, list) | Expression::Or(list) => {
+            Check::Any(list.iter().map(|x| compile(x, bravery, relic)).collect())
+        }
+        Expression::Not(expression) => Check::Not(Box::new(compile(expression, bravery, relic))),
+    }
+}
+
+/// Compiles a condition, or returns `None` if it never matches,
+/// e.g. as it reads a game of a mode that is not selected.
+fn compile_condition(
+    condition: &Condition,
+    bravery: Option<usize>,
+    relic: Option<usize>,
+) -> Option<Check> {
+    let is_in =
+        |value: u32, columns: std::ops::Range<usize>| Check::In(value as u8, columns.collect());
+    let shift = |bravery: usize, position: usize, shift| {
+        Check::Shift(
+            bravery + SHIFT_OFFSET,
+            position as u32,
+            BraveryData::get_shift_positions(shift),
+        )
+    };
+
+    Some(match condition {
+        Condition::Invalid(_) => return None,
+        Condition::MonsterAvailable(id) => is_in(*id, bravery?..bravery? + SHIFT_OFFSET),
+        Condition::RelicAvailable(relic_id) => is_in(*relic_id, relic?..relic? + RELIC_CHESTS),
+        Condition::BraveryChest(id) => is_in(*id, bravery? + AREA_EGGS..bravery? + SHIFT_OFFSET),
+        Condition::Familiar(id) => is_in(*id, bravery?..bravery? + 1),
+        Condition::Starter(id) => is_in(*id, bravery? + 1..bravery? + 3),
+        Condition::Swimming(id) => is_in(*id, bravery? + 3..bravery? + 4),
+        Condition::Bex(id) => is_in(*id, bravery? + 4..bravery? + 5),
+        Condition::Cryomancer(id) => is_in(*id, bravery? + 5..bravery? + 6),
+        Condition::CryomancerRequired(id) => is_in(*id, bravery? + 6..bravery? + 7),
+        Condition::EndOfTime(id) => is_in(*id, bravery? + 7..bravery? + ARMY),
+        Condition::Army(id) => is_in(*id, bravery? + ARMY..bravery? + AREA_EGGS),
+        Condition::MonsterInArea(monster, area) => Check::In(
+            *monster as u8,
+            GAME_DATA.areas[*area as usize]
+                .wild_monsters
+                .iter()
+                .map(|x| RANDOMIZER + *x as usize - 4)
+                .collect(),
+        ),
+        Condition::EggInArea(monster, area) => {
+            let column = bravery? + AREA_EGGS + *area as usize;
+            is_in(*monster, column..column + 1)
+        }
+        Condition::ShiftedEggInArea(monster, area, egg_shift) => {
+            let column = bravery? + AREA_EGGS + *area as usize;
+            let mut checks = vec![shift(bravery?, *area as usize, *egg_shift)];
+
+            checks.extend(monster.map(|x| is_in(x, column..column + 1)));
+            Check::All(checks)
+        }
+        Condition::ShiftedArmy(monster, egg_shift) => Check::Any(
+            (0..7)
+                .map(|i| {
+                    let column = bravery? + ARMY + i;
+                    let army = match monster {
+                        Some(monster) => is_in(*monster, column..column + 1),
+                        None => Check::NotNull(column),
+                    };
+
+                    Some(Check::All(vec![army, shift(bravery?, i, *egg_shift)]))
+                })
+                .collect::<Option<Vec<Check>>>()?,
+        ),
+        Condition::RelicInArea(relic_id, area) => {
+            let column = relic? + *area as usize;
+            is_in(*relic_id, column..column + 1)
+        }
+        Condition::RelicInScene(relic_id, scene, chest) => {
+            let area = GAME_DATA
+                .area_data
+                .iter()
+                .find(|x| x.scene_id == *scene)?
+                .area_id as usize;
+            let column = relic? + RELIC_CHESTS + area * 2;
+
+            let mut checks = vec![
+                is_in(*relic_id, relic? + area..relic? + area + 1),
+                is_in(*scene, column..column + 1),
+            ];
+
+            checks.extend(chest.map(|x| is_in(x, column + 1..column + 2)));
+            Check::All(checks)
+        }
+        Condition::Replacement(monster, replacement) => {
+            let column = RANDOMIZER + *monster as usize - 4;
+            is_in(*replacement, column..column + 1)
+        }
+    })
+}
@@ -1,192 +1,927 @@
 use std::{
-    iter,
+    collections::{BTreeMap, HashMap},
+    env, fs, iter,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc, Arc, LazyLock, Mutex,
     },
+    thread,
 };
 
-use rusqlite::{params, params_from_iter, Connection, Error, Result};
-use slint::{ComponentHandle, PhysicalPosition, PhysicalSize, Weak};
+use rusqlite::{params_from_iter, Connection, Error, OptionalExtension, Result};
 
 use crate::{
-    structs::{game::Game, game_manager},
-    ui::{dialog, enums::condition::Condition, types::ProgressDialog},
+    data::{GAME_DATA, GAME_DATA_HASH, VERSION},
+    seed_finder::{
+        backend::{Page, COLUMNAR_EXTENSION},
+        bitmap::{self, Bitmap, BitmapIndex, BRAVERY_GAMES, RELIC_GAMES},
+        enums::expression::Expression,
+    },
+    structs::{
+        error::GenerationError,
+        game::Game,
+        game_manager,
+        modes::{bravery::BraveryData, relic::RelicData},
+    },
 };
 
+/// Version of the tables' schema, to increment whenever a table or the values stored for a seed change.
+///
+/// 2 added the Bravery egg shifts, 3 the relic chests and 4 changed the tries and draws
+/// of bad seeds, now detected before the eggs are ever rerolled.
+const SCHEMA_VERSION: u32 = 4;
+
+/// Number of seeds generated and committed at once.
+pub(crate) const BATCH_SIZE: u32 = 1000;
+
+/// Conditions without a bitmap are checked seed by seed when at most 1 in this many seeds
+/// is left after the ones with a bitmap, which is faster than reading every row.
+const LOOKUP_RATIO: u64 = 10;
+
+/// Seeds the game generates when starting a new save, stored in the database by default.
+pub const DEFAULT_SEEDS: RangeInclusive<u32> = 0..=999_999;
+
+/// Name of the database used when none is chosen.
+pub const DEFAULT_DATABASE: &str = "seeds";
+
+/// Environment variable overriding the path of the default database.
+pub const DATABASE_ENV: &str = "SANCTUARY_SEEDER_DB";
+
+/// Scene IDs by scene name, to store the chests holding relics.
+static SCENE_IDS: LazyLock<HashMap<&'static str, u32>> = LazyLock::new(|| {
+    GAME_DATA
+        .area_data
+        .iter()
+        .map(|x| (x.scene_name.as_str(), x.scene_id))
+        .collect()
+});
+
+/// All games stored in the database for a single seed.
+pub(crate) struct SeedGames {
+    pub seed: u32,
+    /// Randomizer + Relics
+    randomizer: Result<Game, GenerationError>,
+    /// Bravery + Relics
+    bravery: Result<Game, GenerationError>,
+    /// Randomizer + Bravery + Relics
+    randomizer_bravery: Result<Game, GenerationError>,
+}
+
+/// A row of the database for one of a seed's games.
+pub(crate) struct Row {
+    pub table: &'static str,
+    pub id: i64,
+    /// The game modes of this row's game.
+    pub modes: &'static str,
+    /// The values after the id, or `None` if the game fails to generate and is not stored.
+    pub values: Option<Vec<Option<i64>>>,
+}
+
+impl SeedGames {
+    pub fn new(seed: u32) -> SeedGames {
+        SeedGames {
+            seed,
+            randomizer: game_manager::generate_game(seed, true, false, true),
+            bravery: game_manager::generate_game(seed, false, true, true),
+            randomizer_bravery: game_manager::generate_game(seed, true, true, true),
+        }
+    }
+
+    /// Returns every row of these games.
+    ///
+    /// Bravery and Relic ids are above `u32::MAX` for the highest seeds.
+    pub fn rows(&self) -> [Row; 6] {
+        let seed = self.seed as i64;
+        let row = |table, id, modes, values| Row {
+            table,
+            id,
+            modes,
+            values,
+        };
+
+        [
+            row(
+                "Randomizer",
+                seed,
+                "Randomizer",
+                self.randomizer
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.mapping.as_deref())
+                    .map(randomizer_values),
+            ),
+            row(
+                "Relic",
+                seed * 3,
+                "Randomizer + Relics",
+                self.randomizer
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.relics.as_ref())
+                    .map(relic_values),
+            ),
+            row(
+                "Bravery",
+                seed * 2,
+                "Bravery",
+                self.bravery
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.bravery_data.as_ref())
+                    .map(bravery_values),
+            ),
+            row(
+                "Relic",
+                seed * 3 + 1,
+                "Bravery + Relics",
+                self.bravery
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.relics.as_ref())
+                    .map(relic_values),
+            ),
+            row(
+                "Bravery",
+                seed * 2 + 1,
+                "Randomizer + Bravery",
+                self.randomizer_bravery
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.bravery_data.as_ref())
+                    .map(bravery_values),
+            ),
+            row(
+                "Relic",
+                seed * 3 + 2,
+                "Randomizer + Bravery + Relics",
+                self.randomizer_bravery
+                    .as_ref()
+                    .ok()
+                    .and_then(|x| x.relics.as_ref())
+                    .map(relic_values),
+            ),
+        ]
+    }
+}
+
+/// Generates a range of seeds and stores them in the database.
+///
+/// Seeds are generated on one worker thread per CPU core, while batches are written
+/// in order by the calling thread so that the database is the same as a sequential run.
+///
+/// If a previous generation of the same seeds was stopped, it resumes after the last
+/// committed batch. Should the tables not match that batch or hold other seeds,
+/// they are cleared and generation starts over. Outdated databases are recreated.
+///
+/// `progress` is called with the number of batches done and the total number of batches
+/// after each batch is committed. Setting `stop` cancels the generation with an error.
 pub fn generate(
-    dialog: Weak<ProgressDialog>,
-    parent_position: PhysicalPosition,
-    parent_size: PhysicalSize,
+    path: &Path,
+    seeds: RangeInclusive<u32>,
+    progress: impl Fn(u32, u32),
     stop: Arc<AtomicBool>,
 ) -> Result<()> {
-    create_tables()?;
-
-    let mut conn = Connection::open("seeds.db")?;
-
-    let sql_mapping = format!(
-        "INSERT INTO Randomizer VALUES ({})",
-        std::iter::repeat("?")
-            .take(107)
-            .collect::<Vec<&str>>()
-            .join(",")
-    );
+    // Opening the database reports the error if the directory could not be created
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
 
-    let sql_bravery = format!(
-        "INSERT INTO Bravery VALUES ({})",
-        std::iter::repeat("?")
-            .take(31)
-            .collect::<Vec<&str>>()
-            .join(",")
-    );
+    let mut conn = Connection::open(path)?;
 
-    let sql_relic = format!(
-        "INSERT INTO Relic VALUES ({})",
-        std::iter::repeat("?")
-            .take(14)
-            .collect::<Vec<&str>>()
-            .join(",")
-    );
+    if outdated(&conn)?.is_some() {
+        drop_tables(&conn)?;
+    }
 
-    // For transactions, let's handle 1000 seeds at a time
-    for i in 0..1000 {
-        let tx = conn.transaction()?;
-        let mut randomizer = tx.prepare(&sql_mapping)?;
-        let mut bravery = tx.prepare(&sql_bravery)?;
-        let mut relic = tx.prepare(&sql_relic)?;
-
-        let mut insert_bravery = |id: u32, game: &Game| -> Result<()> {
-            if let Some(data) = &game.bravery_data {
-                bravery.execute(params!(
-                    id,
-                    data.familiar,
-                    data.starters[1],
-                    data.starters[2],
-                    data.swimming,
-                    data.bex,
-                    data.cryomancer.unwrap(),
-                    data.cryomancer_required,
-                    data.end_of_time[0],
-                    data.end_of_time[1],
-                    data.end_of_time[2],
-                    data.army[0],
-                    data.army[1],
-                    data.army[2],
-                    data.army[3],
-                    data.army[4],
-                    data.army[5],
-                    data.army.get(6),
-                    data.eggs[0],
-                    data.eggs[1],
-                    data.eggs[2],
-                    data.eggs[3],
-                    data.eggs[4],
-                    data.eggs[5],
-                    data.eggs[6],
-                    data.eggs[7],
-                    data.eggs[8],
-                    data.eggs[9],
-                    data.eggs[10],
-                    data.eggs[11],
-                    data.eggs[12],
-                ))?;
-            }
+    create_tables(&conn)?;
 
-            Ok(())
-        };
+    let start = match committed_batches(&conn, &seeds)? {
+        Some(batches) => batches,
+        None => {
+            clear_tables(&conn)?;
+            0
+        }
+    };
+
+    set_seed_range(&conn, &seeds)?;
+
+    generate_batches(
+        &seeds,
+        start,
+        SeedGames::new,
+        progress,
+        &stop,
+        || Error::ExecuteReturnedResults,
+        |batch, games| insert_batch(&mut conn, batch, games),
+    )?;
+
+    // Also built for databases generated before bitmaps existed
+    if !bitmap::has_bitmaps(&conn)? {
+        bitmap::build(&mut conn, &seeds)?;
+    }
 
-        for j in 0..1000 {
-            let seed = i * 1000 + j;
-            let game = game_manager::generate_game(seed, true, false, true);
+    Ok(())
+}
 
-            let mut params = vec![Some(seed)];
-            params.extend(game.mapping.unwrap().iter().skip(4).take(106));
+/// Calls `generate` for each seed of a range in batches from the `start` batch, on one worker
+/// thread per CPU core, and passes the results of each batch to `write` in order on the calling thread.
+///
+/// `progress` is called with the number of batches done and the total number of batches
+/// after each batch is written. Setting `stop` cancels the generation with `stopped()`.
+pub(crate) fn generate_batches<T: Send, E>(
+    seeds: &RangeInclusive<u32>,
+    start: u32,
+    generate: impl Fn(u32) -> T + Sync,
+    progress: impl Fn(u32, u32),
+    stop: &AtomicBool,
+    stopped: fn() -> E,
+    mut write: impl FnMut(u32, &[T]) -> std::result::Result<(), E>,
+) -> std::result::Result<(), E> {
+    let batch_count = batch_count(seeds);
+
+    progress(start, batch_count);
+
+    let next_batch = AtomicU32::new(start);
+    let workers = thread::available_parallelism().map_or(1, |x| x.get());
+
+    thread::scope(|scope| {
+        // Bounded, so that workers cannot get too far ahead of the database
+        let (sender, receiver) = mpsc::sync_channel::<(u32, Vec<T>)>(workers * 2);
+
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next_batch = &next_batch;
+            let generate = &generate;
+
+            scope.spawn(move || loop {
+                let batch = next_batch.fetch_add(1, Ordering::Relaxed);
+
+                if batch >= batch_count || stop.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let first = *seeds.start() as u64 + batch as u64 * BATCH_SIZE as u64;
+                let last = (first + BATCH_SIZE as u64 - 1).min(*seeds.end() as u64);
+
+                let games = (first..=last).map(|x| generate(x as u32)).collect();
+
+                // The writer stopped, no need to generate anything else
+                if sender.send((batch, games)).is_err() {
+                    break;
+                }
+            });
+        }
 
-            // Randomizer
-            randomizer.execute(params_from_iter(params))?;
+        drop(sender);
 
-            // Randomizer + Relics
-            relic.execute(params_from_iter(
-                iter::once(seed * 3).chain(game.relics.unwrap().list),
-            ))?;
+        // Batches can be received in any order, only write them in sequence
+        let mut pending = BTreeMap::new();
+        let mut next = start;
 
-            // Bravery
-            let game = game_manager::generate_game(seed, false, true, true);
-            insert_bravery(seed * 2, &game)?;
+        for (batch, games) in receiver {
+            pending.insert(batch, games);
 
-            // Bravery + Relics
-            relic.execute(params_from_iter(
-                iter::once(seed * 3 + 1).chain(game.relics.unwrap().list),
-            ))?;
+            while let Some(games) = pending.remove(&next) {
+                write(next, &games)?;
+                next += 1;
 
-            // Randomizer + Bravery
-            let game = game_manager::generate_game(seed, true, true, true);
-            insert_bravery(seed * 2 + 1, &game)?;
+                if stop.load(Ordering::Relaxed) {
+                    return Err(stopped());
+                }
 
-            // Randomizer + Bravery + Relics
-            if let Some(relics) = game.relics {
-                relic.execute(params_from_iter(
-                    iter::once(seed * 3 + 2).chain(relics.list),
-                ))?;
+                progress(next, batch_count);
             }
         }
 
-        randomizer.finalize()?;
-        bravery.finalize()?;
-        relic.finalize()?;
-        tx.commit()?;
+        // Workers may have stopped before the writer noticed
+        if next < batch_count {
+            return Err(stopped());
+        }
+
+        Ok(())
+    })
+}
 
-        if stop.load(Ordering::Relaxed) {
-            // return Ok(());
-            return Err(Error::ExecuteReturnedResults);
+/// Inserts all games of a batch of seeds within a single transaction.
+///
+/// The batch is also recorded as the last one committed, to resume generation from it.
+pub(crate) fn insert_batch(conn: &mut Connection, index: u32, batch: &[SeedGames]) -> Result<()> {
+    let tx = conn.transaction()?;
+    let mut randomizer = tx.prepare(&insert_sql("Randomizer", 107))?;
+    let mut bravery = tx.prepare(&insert_sql("Bravery", 32))?;
+    let mut relic = tx.prepare(&insert_sql("Relic", 40))?;
+
+    for games in batch {
+        for row in games.rows() {
+            let Some(values) = row.values else {
+                continue;
+            };
+
+            let stmt = match row.table {
+                "Randomizer" => &mut randomizer,
+                "Bravery" => &mut bravery,
+                _ => &mut relic,
+            };
+
+            stmt.execute(params_from_iter(iter::once(Some(row.id)).chain(values)))?;
         }
+    }
+
+    randomizer.finalize()?;
+    bravery.finalize()?;
+    relic.finalize()?;
 
-        // Update UI
-        let progress = i as f32 / 1000.;
-        let progress_text = i as i32 / 10;
+    write_metadata(&tx, "Batches", index + 1)?;
+    tx.commit()
+}
+
+/// Returns the values of a `Randomizer` row: the replacement of each monster after the spectral familiars.
+pub(crate) fn randomizer_values(mapping: &[Option<u32>]) -> Vec<Option<i64>> {
+    mapping
+        .iter()
+        .skip(4)
+        .take(106)
+        .map(|x| x.map(i64::from))
+        .collect()
+}
+
+/// Returns the values of a `Bravery` row, in the order of its columns.
+pub(crate) fn bravery_values(data: &BraveryData) -> Vec<Option<i64>> {
+    [
+        data.familiar,
+        data.starters[1],
+        data.starters[2],
+        data.swimming,
+        data.bex,
+        data.cryomancer.unwrap(),
+        data.cryomancer_required,
+    ]
+    .into_iter()
+    .chain(data.end_of_time.iter().take(3).copied())
+    .map(Some)
+    .chain((0..7).map(|x| data.army.get(x).copied().flatten()))
+    .chain(data.eggs.iter().take(13).map(|x| Some(*x)))
+    .chain(iter::once(Some(data.shift_offset)))
+    .map(|x| x.map(i64::from))
+    .collect()
+}
 
-        dialog
-            .upgrade_in_event_loop(move |dialog| {
-                dialog.set_progress(progress);
-                dialog.set_progress_text(progress_text);
-            })
-            .unwrap();
+/// Returns the values of a `Relic` row: the relic of each area, then the scene and chest holding it.
+pub(crate) fn relic_values(relics: &RelicData) -> Vec<Option<i64>> {
+    relics
+        .list
+        .iter()
+        .map(|x| *x as i64)
+        .chain(
+            relics
+                .area_chests
+                .iter()
+                .flat_map(|(scene, chest)| [SCENE_IDS[scene.as_str()] as i64, *chest as i64]),
+        )
+        .map(Some)
+        .collect()
+}
+
+/// Returns the directory holding the named databases.
+///
+/// This is `$XDG_DATA_HOME/sanctuary-seeder` or `~/.local/share/sanctuary-seeder` on Linux,
+/// `%APPDATA%\sanctuary-seeder` on Windows and `~/Library/Application Support/sanctuary-seeder`
+/// on macOS, falling back to the working directory.
+pub fn data_dir() -> PathBuf {
+    let var = |key: &str| {
+        env::var_os(key)
+            .filter(|x| !x.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|x| x.join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|x| x.join(".local/share")))
+    };
+
+    base.map_or(PathBuf::from("."), |x| x.join("sanctuary-seeder"))
+}
+
+/// Returns the path of the default database.
+///
+/// This is the path set in `SANCTUARY_SEEDER_DB` if any, then `seeds.db` in the
+/// working directory if an earlier version created it there, then `seeds.db` in the data directory.
+pub fn default_path() -> PathBuf {
+    if let Some(path) = env::var_os(DATABASE_ENV).filter(|x| !x.is_empty()) {
+        return PathBuf::from(path);
+    }
+
+    let legacy = PathBuf::from(format!("{DEFAULT_DATABASE}.db"));
+
+    if legacy.is_file() {
+        return legacy;
+    }
+
+    data_dir().join(legacy)
+}
+
+/// Returns the path of a database from its name in the data directory, or the path itself.
+///
+/// Names ending with `.idx` are columnar databases, and any other name a SQLite database.
+pub fn database_path(name: &str) -> PathBuf {
+    if name == DEFAULT_DATABASE {
+        return default_path();
     }
 
-    dialog
-        .upgrade_in_event_loop(move |dialog| {
-            dialog.hide().unwrap();
-            dialog::show_message(
-                String::from("Database generated with success!"),
-                parent_position,
-                parent_size,
-            );
+    let path = Path::new(name);
+
+    if path.components().count() > 1 {
+        return path.to_path_buf();
+    }
+
+    if path
+        .extension()
+        .is_some_and(|x| x == "db" || x == COLUMNAR_EXTENSION)
+    {
+        return data_dir().join(name);
+    }
+
+    data_dir().join(format!("{name}.db"))
+}
+
+/// Returns the names of the databases in the data directory, starting with the default one.
+///
+/// SQLite databases are named without their extension.
+pub fn databases() -> Vec<String> {
+    let mut names = fs::read_dir(data_dir())
+        .map(|dir| {
+            dir.filter_map(|x| x.ok().map(|x| x.path()))
+                .filter_map(|x| match x.extension()?.to_str()? {
+                    "db" => Some(x.file_stem()?.to_str()?.to_owned()),
+                    COLUMNAR_EXTENSION => Some(x.file_name()?.to_str()?.to_owned()),
+                    _ => None,
+                })
+                .filter(|x| x != DEFAULT_DATABASE)
+                .collect::<Vec<String>>()
         })
-        .unwrap();
+        .unwrap_or_default();
+
+    names.sort();
+    names.insert(0, String::from(DEFAULT_DATABASE));
+    names
+}
+
+/// Returns why the database cannot be used by this version of the generator, if it cannot.
+///
+/// Such a database may hold other columns or seeds, and has to be generated again.
+pub fn outdated_reason(path: &Path) -> Result<Option<String>> {
+    outdated(&Connection::open(path)?)
+}
+
+pub(crate) fn outdated(conn: &Connection) -> Result<Option<String>> {
+    if !has_metadata(conn)? {
+        return Ok(Some(String::from("it was not created by this generator")));
+    }
+
+    let schema = read_metadata(conn, "SchemaVersion")?;
+    let generator = read_metadata(conn, "GeneratorVersion")?;
+    let game_data = read_metadata(conn, "GameDataHash")?;
+
+    Ok(match (schema, generator, game_data) {
+        (None, _, _) | (_, None, _) | (_, _, None) => Some(String::from(
+            "it was created by an earlier version of the generator",
+        )),
+        (Some(schema), _, _) if schema != SCHEMA_VERSION.to_string() => Some(format!(
+            "its schema version is {schema} instead of {SCHEMA_VERSION}"
+        )),
+        (_, Some(generator), _) if generator != VERSION => Some(format!(
+            "it was created by version {generator} of the generator instead of {VERSION}"
+        )),
+        (_, _, Some(game_data)) if game_data != GAME_DATA_HASH => {
+            Some(String::from("it was created from other game data"))
+        }
+        _ => None,
+    })
+}
+
+/// Returns true if the database holds every seed of its range.
+pub fn is_complete(path: &Path) -> Result<bool> {
+    let conn = Connection::open(path)?;
+
+    if outdated(&conn)?.is_some() {
+        return Ok(false);
+    }
+
+    let Some(seeds) = read_seed_range(&conn)? else {
+        return Ok(false);
+    };
+
+    // Bitmaps are only built once every batch is committed, and are cleared with the tables,
+    // which saves counting every row
+    if bitmap::has_bitmaps(&conn)? {
+        return Ok(read_metadata(&conn, "Batches")? == Some(batch_count(&seeds).to_string()));
+    }
+
+    Ok(committed_batches(&conn, &seeds)? == Some(batch_count(&seeds)))
+}
+
+/// Returns the range of seeds the database holds, or is being generated with,
+/// or `None` if it could not be read.
+pub fn seed_range(path: &Path) -> Result<Option<RangeInclusive<u32>>> {
+    let conn = Connection::open(path)?;
+
+    if !has_metadata(&conn)? {
+        return Ok(None);
+    }
+
+    read_seed_range(&conn)
+}
+
+fn read_seed_range(conn: &Connection) -> Result<Option<RangeInclusive<u32>>> {
+    let first = read_metadata(conn, "FirstSeed")?;
+    let last = read_metadata(conn, "LastSeed")?;
+
+    match (
+        first.map(|x| x.parse::<u32>()),
+        last.map(|x| x.parse::<u32>()),
+    ) {
+        (Some(Ok(first)), Some(Ok(last))) => Ok(Some(first..=last)),
+        _ => Ok(None),
+    }
+}
+
+/// Records the range of seeds the database is generated with.
+fn set_seed_range(conn: &Connection, seeds: &RangeInclusive<u32>) -> Result<()> {
+    write_metadata(conn, "FirstSeed", seeds.start())?;
+    write_metadata(conn, "LastSeed", seeds.end())
+}
+
+pub(crate) fn has_metadata(conn: &Connection) -> Result<bool> {
+    has_table(conn, "Metadata")
+}
+
+pub(crate) fn has_table(conn: &Connection, table: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        [table],
+        |row| row.get::<usize, u32>(0),
+    )
+    .map(|x| x > 0)
+}
+
+fn read_metadata(conn: &Connection, key: &str) -> Result<Option<String>> {
+    conn.query_row("SELECT Value FROM Metadata WHERE Key = ?", [key], |row| {
+        row.get::<usize, String>(0)
+    })
+    .optional()
+}
+
+fn write_metadata(conn: &Connection, key: &str, value: impl ToString) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO Metadata VALUES (?, ?)",
+        [key, &value.to_string()],
+    )?;
 
     Ok(())
 }
 
+/// Returns the number of batches needed to generate a range of seeds.
+pub(crate) fn batch_count(seeds: &RangeInclusive<u32>) -> u32 {
+    seed_count(seeds).div_ceil(BATCH_SIZE as u64) as u32
+}
+
+pub(crate) fn seed_count(seeds: &RangeInclusive<u32>) -> u64 {
+    (*seeds.end() as u64 + 1).saturating_sub(*seeds.start() as u64)
+}
+
+/// Returns the number of batches of a range of seeds committed to the database,
+/// or `None` if the tables do not hold exactly these batches.
+fn committed_batches(conn: &Connection, seeds: &RangeInclusive<u32>) -> Result<Option<u32>> {
+    if read_seed_range(conn)?.as_ref() != Some(seeds) {
+        return Ok(None);
+    }
+
+    let batches = match read_metadata(conn, "Batches")?.map(|x| x.parse::<u32>()) {
+        Some(Ok(batches)) => batches,
+        Some(Err(_)) => return Ok(None),
+        None => 0,
+    };
+
+    let first = *seeds.start() as i64;
+    let count = (batches as u64 * BATCH_SIZE as u64).min(seed_count(seeds)) as i64;
+    let end = first + count;
+
+    // Every seed has a Randomizer and a Randomizer + Relics row, while the Bravery modes
+    // may fail each on their own
+    let (randomizer, randomizer_min, randomizer_max) = conn.query_row(
+        "SELECT COUNT(*), IFNULL(MIN(Id), -1), IFNULL(MAX(Id), -1) FROM Randomizer",
+        [],
+        |row| {
+            Ok((
+                row.get::<usize, i64>(0)?,
+                row.get::<usize, i64>(1)?,
+                row.get::<usize, i64>(2)?,
+            ))
+        },
+    )?;
+
+    let (bravery, randomizer_bravery, bravery_min, bravery_max) = conn.query_row(
+        "SELECT SUM(Id % 2 = 0), SUM(Id % 2 = 1), IFNULL(MIN(Id), -1), IFNULL(MAX(Id), -1) FROM Bravery",
+        [],
+        |row| {
+            Ok((
+                row.get::<usize, Option<i64>>(0)?.unwrap_or(0),
+                row.get::<usize, Option<i64>>(1)?.unwrap_or(0),
+                row.get::<usize, i64>(2)?,
+                row.get::<usize, i64>(3)?,
+            ))
+        },
+    )?;
+
+    // Relic rows of the Bravery games are counted per game, and again only when the Bravery row
+    // of the same seed exists: each seed missing a Bravery row then misses the matching Relic row
+    let (
+        randomizer_relic,
+        bravery_relic,
+        randomizer_bravery_relic,
+        bravery_relic_matched,
+        randomizer_bravery_relic_matched,
+        relic_min,
+        relic_max,
+    ) = conn.query_row(
+        "SELECT
+            SUM(Id % 3 = 0),
+            SUM(Id % 3 = 1),
+            SUM(Id % 3 = 2),
+            SUM(Id % 3 = 1 AND EXISTS (SELECT 1 FROM Bravery WHERE Bravery.Id = Relic.Id / 3 * 2)),
+            SUM(Id % 3 = 2 AND EXISTS (SELECT 1 FROM Bravery WHERE Bravery.Id = Relic.Id / 3 * 2 + 1)),
+            IFNULL(MIN(Id), -1),
+            IFNULL(MAX(Id), -1)
+        FROM Relic",
+        [],
+        |row| {
+            Ok((
+                row.get::<usize, Option<i64>>(0)?.unwrap_or(0),
+                row.get::<usize, Option<i64>>(1)?.unwrap_or(0),
+                row.get::<usize, Option<i64>>(2)?.unwrap_or(0),
+                row.get::<usize, Option<i64>>(3)?.unwrap_or(0),
+                row.get::<usize, Option<i64>>(4)?.unwrap_or(0),
+                row.get::<usize, i64>(5)?,
+                row.get::<usize, i64>(6)?,
+            ))
+        },
+    )?;
+
+    let is_consistent = randomizer == count
+        && randomizer_relic == count
+        && bravery <= count
+        && bravery_relic == bravery
+        && bravery_relic_matched == bravery
+        && randomizer_bravery <= count
+        && randomizer_bravery_relic == randomizer_bravery
+        && randomizer_bravery_relic_matched == randomizer_bravery
+        && (count == 0
+            || (randomizer_min == first
+                && randomizer_max == end - 1
+                && bravery_min >= first * 2
+                && bravery_max < end * 2
+                && relic_min >= first * 3
+                && relic_max < end * 3));
+
+    Ok(is_consistent.then_some(batches))
+}
+
+/// Deletes all seeds from the database.
+pub(crate) fn clear_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DELETE FROM Randomizer;
+        DELETE FROM Bravery;
+        DELETE FROM Relic;
+        DELETE FROM Bitmap;
+        DELETE FROM Metadata WHERE Key = 'Batches';",
+    )
+}
+
+/// Returns the columns of a table after its id.
+pub(crate) fn column_names(table: &str) -> Result<Vec<String>> {
+    let conn = Connection::open_in_memory()?;
+    create_tables(&conn)?;
+
+    // Table names only ever come from the known tables
+    let stmt = conn.prepare(&format!("SELECT * FROM {table}"))?;
+
+    Ok(stmt
+        .column_names()
+        .iter()
+        .skip(1)
+        .map(|x| x.to_string())
+        .collect())
+}
+
+/// Deletes all tables from the database, to create them again with the current schema.
+fn drop_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DROP TABLE IF EXISTS Randomizer;
+        DROP TABLE IF EXISTS Bravery;
+        DROP TABLE IF EXISTS Relic;
+        DROP TABLE IF EXISTS Bitmap;
+        DROP TABLE IF EXISTS BadSeed;
+        DROP TABLE IF EXISTS Metadata;",
+    )
+}
+
+/// Returns an `INSERT` statement for a table, with a parameter for each column.
+fn insert_sql(table: &str, columns: usize) -> String {
+    format!(
+        "INSERT INTO {table} VALUES ({})",
+        iter::repeat_n("?", columns)
+            .collect::<Vec<&str>>()
+            .join(",")
+    )
+}
+
 pub fn find_seeds(
+    path: &Path,
     is_randomizer: bool,
     is_bravery: bool,
     is_relic: bool,
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
 ) -> Result<Vec<u32>> {
-    let conn = Connection::open("seeds.db")?;
-    let sql = build_query(is_randomizer, is_bravery, is_relic, conditions);
+    find_seeds_page(path, is_randomizer, is_bravery, is_relic, conditions, None)
+}
 
-    let mut stmt = conn.prepare(&sql)?;
-    let rows = stmt.query_map([], |row| Ok(row.get::<usize, u32>(0)))?;
-    let seeds = rows.map(|x| x.unwrap().unwrap()).collect::<Vec<u32>>();
+/// Finds the seeds matching the conditions in order, only keeping those of a page if any.
+pub fn find_seeds_page(
+    path: &Path,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    page: Option<Page>,
+) -> Result<Vec<u32>> {
+    let conn = Connection::open(path)?;
+    let conditions = conditions.lock().unwrap().clone();
+    let (offset, limit) = page.map_or((0, usize::MAX), |x| (x.offset as usize, x.limit as usize));
+
+    match matching(path, is_randomizer, is_bravery, is_relic, conditions)? {
+        Matching::Bitmap(seeds) => Ok(seeds.seeds().skip(offset).take(limit).collect()),
+        Matching::Lookup(seeds, remaining) => {
+            let mut found = vec![];
+            let mut skipped = 0;
+
+            lookup(
+                &conn,
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                &seeds,
+                &remaining,
+                |seed| {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if found.len() < limit {
+                        found.push(seed);
+                    }
+
+                    found.len() < limit
+                },
+            )?;
+
+            Ok(found)
+        }
+        Matching::Query(conditions) => {
+            let (mut sql, mut params) =
+                build_query(is_randomizer, is_bravery, is_relic, &conditions);
+
+            // Randomizer.Id is the primary key, ordering by it costs nothing
+            if let Some(page) = page {
+                sql += " ORDER BY Randomizer.Id LIMIT ? OFFSET ?";
+                params.extend([page.limit, page.offset]);
+            }
 
-    Ok(seeds)
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map(params_from_iter(params), |row| row.get::<usize, u32>(0))?;
+
+            rows.collect()
+        }
+    }
+}
+
+/// Returns the number of seeds matching the conditions.
+pub fn count_seeds(
+    path: &Path,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+) -> Result<u64> {
+    let conn = Connection::open(path)?;
+    let conditions = conditions.lock().unwrap().clone();
+
+    match matching(path, is_randomizer, is_bravery, is_relic, conditions)? {
+        Matching::Bitmap(seeds) => Ok(seeds.count()),
+        Matching::Lookup(seeds, remaining) => {
+            let mut count = 0;
+
+            lookup(
+                &conn,
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                &seeds,
+                &remaining,
+                |_| {
+                    count += 1;
+                    true
+                },
+            )?;
+
+            Ok(count)
+        }
+        Matching::Query(conditions) => {
+            let (sql, params) = build_query(is_randomizer, is_bravery, is_relic, &conditions);
+
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM ({sql})"),
+                params_from_iter(params),
+                |row| row.get::<usize, u64>(0),
+            )
+        }
+    }
+}
+
+/// How the seeds matching the conditions are found.
+enum Matching {
+    /// Every seed of the bitmap matches.
+    Bitmap(Bitmap),
+    /// Seeds of the bitmap match if they also match the remaining conditions.
+    Lookup(Bitmap, Vec<Expression>),
+    /// The query checks the conditions on every row.
+    Query(Vec<Expression>),
 }
 
-/// Creates all tables for the database.
-fn create_tables() -> Result<()> {
-    let conn = Connection::open("seeds.db")?;
+/// Finds how to match the conditions, using the bitmaps of the database if it has any.
+fn matching(
+    path: &Path,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Vec<Expression>,
+) -> Result<Matching> {
+    let Some(mut index) = BitmapIndex::open(path)? else {
+        return Ok(Matching::Query(conditions));
+    };
+
+    let bravery = is_bravery.then_some(BRAVERY_GAMES[is_randomizer as usize]);
+    let relic = is_relic.then_some(RELIC_GAMES[relic_game(is_randomizer, is_bravery)]);
+
+    let mut seeds = index.stored(bravery, relic)?;
+    let mut remaining = vec![];
+
+    for condition in &conditions {
+        match index.evaluate(condition, bravery, relic)? {
+            Some(bitmap) => seeds.intersect(&bitmap),
+            None => remaining.push(condition.clone()),
+        }
+    }
 
+    Ok(if remaining.is_empty() {
+        Matching::Bitmap(seeds)
+    } else if seeds.count() * LOOKUP_RATIO <= seed_count(index.seed_range()) {
+        Matching::Lookup(seeds, remaining)
+    } else {
+        Matching::Query(conditions)
+    })
+}
+
+/// Checks the seeds of a bitmap against the remaining conditions one by one, in order,
+/// passing those matching to `found` until it returns false.
+fn lookup(
+    conn: &Connection,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    seeds: &Bitmap,
+    remaining: &[Expression],
+    mut found: impl FnMut(u32) -> bool,
+) -> Result<()> {
+    let (sql, mut params) = build_query(is_randomizer, is_bravery, is_relic, remaining);
+    let mut stmt = conn.prepare(&format!("{sql} AND Randomizer.Id = ?"))?;
+
+    params.push(0);
+
+    for seed in seeds.seeds() {
+        *params.last_mut().unwrap() = seed;
+
+        if stmt.exists(params_from_iter(&params))? && !found(seed) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates all tables for the database, recording the versions it is generated with.
+pub(crate) fn create_tables(conn: &Connection) -> Result<()> {
     // Randomizer
     let sql = format!(
         "CREATE TABLE IF NOT EXISTS Randomizer (\"Id\" INTEGER NOT NULL,{},PRIMARY KEY(\"Id\"))",
@@ -206,16 +941,30 @@ fn create_tables() -> Result<()> {
     let sql = std::include_str!("../../res/out/tables/Relic.sql");
     conn.execute(sql, ())?;
 
-    Ok(())
+    // Bitmap
+    let sql = std::include_str!("../../res/out/tables/Bitmap.sql");
+    conn.execute(sql, ())?;
+
+    // Bad seeds
+    let sql = std::include_str!("../../res/out/tables/BadSeed.sql");
+    conn.execute(sql, ())?;
+
+    // Metadata
+    let sql = std::include_str!("../../res/out/tables/Metadata.sql");
+    conn.execute(sql, ())?;
+
+    write_metadata(conn, "SchemaVersion", SCHEMA_VERSION)?;
+    write_metadata(conn, "GeneratorVersion", VERSION)?;
+    write_metadata(conn, "GameDataHash", GAME_DATA_HASH)
 }
 
-/// Builds the full query to find seeds.
-fn build_query(
+/// Builds the full query to find seeds, along with the values bound to its parameters.
+pub(crate) fn build_query(
     is_randomizer: bool,
     is_bravery: bool,
     is_relic: bool,
-    conditions: Arc<Mutex<Vec<Condition>>>,
-) -> String {
+    conditions: &[Expression],
+) -> (String, Vec<u32>) {
     // As the Randomizer mapping is determined first,
     // there's only 1 mapping for all game mode combination per seed,
     // making Randomizer.Id always equals to the seed
@@ -239,20 +988,25 @@ fn build_query(
         }
     }
 
-    query += &build_query_conditions(conditions);
-    query
+    let mut params = vec![];
+
+    query += &build_query_conditions(conditions, &mut params);
+    (query, params)
 }
 
-/// Builds the `WHERE` clause of the sql query.
-fn build_query_conditions(conditions: Arc<Mutex<Vec<Condition>>>) -> String {
+/// Builds the `WHERE` clause of the sql query, all conditions being required.
+fn build_query_conditions(conditions: &[Expression], params: &mut Vec<u32>) -> String {
     format!(
         "WHERE {}",
-        conditions
-            .lock()
-            .unwrap()
-            .iter()
-            .map(|x| x.to_sql())
-            .collect::<Vec<String>>()
-            .join(" AND ")
+        Expression::And(conditions.to_vec()).to_sql(params)
     )
 }
+
+/// Returns the index in `RELIC_GAMES` of the Relic game joined for the selected modes.
+pub(crate) fn relic_game(is_randomizer: bool, is_bravery: bool) -> usize {
+    match (is_randomizer, is_bravery) {
+        (true, true) => 2,
+        (false, true) => 1,
+        _ => 0,
+    }
+}
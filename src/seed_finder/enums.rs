@@ -1,3 +1,4 @@
 pub mod condition;
 pub mod effect;
+pub mod expression;
 pub mod value;
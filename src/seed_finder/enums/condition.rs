@@ -0,0 +1,697 @@
+use std::{fmt, str::FromStr, sync::LazyLock};
+
+use crate::{
+    data::{
+        macros::{monster, relic},
+        GAME_DATA,
+    },
+    seed_finder::{
+        db,
+        enums::{effect::Effect, value::Value},
+    },
+    structs::{
+        game::Game,
+        map::AreaData,
+        modes::bravery::{BraveryData, Shift},
+    },
+};
+
+static AREA_COLUMNS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    GAME_DATA
+        .areas
+        .iter()
+        .map(|x| x.name.replace(" ", ""))
+        .collect::<Vec<String>>()
+});
+
+static RELIC_COLUMNS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    AREA_COLUMNS
+        .iter()
+        .map(|x| String::from("Relic.") + x)
+        .collect::<Vec<String>>()
+});
+
+/// Columns of the `Relic` table holding the scene of each area's relic chest.
+static RELIC_SCENE_COLUMNS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    RELIC_COLUMNS
+        .iter()
+        .map(|x| format!("{x}Scene"))
+        .collect::<Vec<String>>()
+});
+
+/// Columns of the `Relic` table holding the chest of each area's relic.
+static RELIC_CHEST_COLUMNS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    RELIC_COLUMNS
+        .iter()
+        .map(|x| format!("{x}Chest"))
+        .collect::<Vec<String>>()
+});
+
+/// Columns of the `Randomizer` table, each holding the replacement of a monster
+/// starting from the 1st monster after the spectral familiars.
+static RANDOMIZER_COLUMNS: LazyLock<Vec<String>> =
+    LazyLock::new(|| (0..106).map(|x| format!("Randomizer.M{x}")).collect());
+
+/// Index of the first area egg in `BRAVERY_COLUMNS`.
+const BRAVERY_AREAS: usize = 17;
+
+static BRAVERY_COLUMNS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    vec![
+        "Familiar",
+        "Start1",
+        "Start2",
+        "Swimming",
+        "Bex",
+        "Cryomancer",
+        "CryomancerRequired",
+        "EndOfTime1",
+        "EndOfTime2",
+        "EndOfTime3",
+        "Army1",
+        "Army2",
+        "Army3",
+        "Army4",
+        "Army5",
+        "Army6",
+        "Army7",
+    ]
+    .iter()
+    .map(|x| String::from(*x))
+    .chain(AREA_COLUMNS.iter().map(|x| String::from("Bravery.") + x))
+    .collect::<Vec<String>>()
+});
+
+/// A condition for the seed finder, to use in the SQL query or to match generated games.
+#[derive(Clone)]
+pub enum Condition {
+    Invalid(String),
+    MonsterAvailable(u32),
+    RelicAvailable(u32),
+    BraveryChest(u32),
+    Familiar(u32),
+    Starter(u32),
+    Swimming(u32),
+    Bex(u32),
+    CryomancerRequired(u32),
+    Cryomancer(u32),
+    Army(u32),
+    EndOfTime(u32),
+    MonsterInArea(u32, u32),
+    EggInArea(u32, u32),
+    /// A monster's egg, or any egg if `None`, with a shift in an area.
+    ShiftedEggInArea(Option<u32>, u32, Shift),
+    /// A monster's egg, or any egg if `None`, with a shift in the Bravery Monster Army.
+    ShiftedArmy(Option<u32>, Shift),
+    RelicInArea(u32, u32),
+    /// A relic in a scene, and optionally in one of its chests.
+    RelicInScene(u32, u32, Option<u32>),
+    Replacement(u32, u32),
+}
+
+impl From<(Value, Effect)> for Condition {
+    fn from((value, effect): (Value, Effect)) -> Self {
+        match value {
+            Value::Monster(monster) => match effect {
+                Effect::None => Condition::Invalid(String::from(
+                    "Could not determine condition: no effect found.",
+                )),
+                Effect::Available => Condition::MonsterAvailable(monster),
+                Effect::BraveryChest => Condition::BraveryChest(monster),
+                Effect::Familiar => Condition::Familiar(monster),
+                Effect::Starter => {
+                    if monster <= 3 {
+                        Condition::Familiar(monster)
+                    } else {
+                        Condition::Starter(monster)
+                    }
+                }
+                Effect::Swimming => Condition::Swimming(monster),
+                Effect::Bex => Condition::Bex(monster),
+                Effect::Cryomancer => Condition::Cryomancer(monster),
+                Effect::CryomancerRequired => Condition::CryomancerRequired(monster),
+                Effect::EndOfTime => Condition::EndOfTime(monster),
+                Effect::Army => Condition::Army(monster),
+                Effect::InArea(area) => Condition::MonsterInArea(monster, area),
+                Effect::InScene(_, _) => {
+                    Condition::Invalid(String::from("Only relics can be searched for in a scene."))
+                }
+                Effect::EggInArea(area) => Condition::EggInArea(monster, area),
+                Effect::ShiftedEggInArea(area, shift) => {
+                    Condition::ShiftedEggInArea(Some(monster), area, shift)
+                }
+                Effect::ShiftedArmy(shift) => Condition::ShiftedArmy(Some(monster), shift),
+                Effect::Replacement(replacement) => Condition::Replacement(monster, replacement),
+            },
+            Value::Relic(relic) => match effect {
+                Effect::Available => Condition::RelicAvailable(relic),
+                Effect::InArea(area) => Condition::RelicInArea(relic, area),
+                Effect::InScene(scene, chest) => Condition::RelicInScene(relic, scene, chest),
+                _ => Condition::Invalid(String::from("Cannot use this condition for a Relic.")),
+            },
+            Value::AnyMonster => match effect {
+                Effect::ShiftedEggInArea(area, shift) => {
+                    Condition::ShiftedEggInArea(None, area, shift)
+                }
+                Effect::ShiftedArmy(shift) => Condition::ShiftedArmy(None, shift),
+                _ => {
+                    Condition::Invalid(String::from("Only egg shifts can be used for any monster."))
+                }
+            },
+            Value::MonsterType(_) | Value::Ability(_) => Condition::Invalid(String::from(
+                "A group of monsters must be expanded into several conditions.",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Condition::Invalid(error) => write!(f, "{error}"),
+            Condition::MonsterAvailable(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Available)
+            }
+            Condition::RelicAvailable(relic) => {
+                write!(f, "{} {}", relic!(*relic).name, Effect::Available)
+            }
+            Condition::BraveryChest(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::BraveryChest)
+            }
+            Condition::Familiar(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Familiar)
+            }
+            Condition::Starter(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Starter)
+            }
+            Condition::Swimming(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Swimming)
+            }
+            Condition::Bex(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Bex)
+            }
+            Condition::Cryomancer(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Cryomancer)
+            }
+            Condition::CryomancerRequired(monster) => {
+                write!(
+                    f,
+                    "{} {}",
+                    monster!(*monster).name,
+                    Effect::CryomancerRequired
+                )
+            }
+            Condition::Army(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::Army)
+            }
+            Condition::EndOfTime(monster) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::EndOfTime)
+            }
+            Condition::MonsterInArea(monster, area) => {
+                write!(f, "{} {}", monster!(*monster).name, Effect::InArea(*area))
+            }
+            Condition::EggInArea(monster, area) => {
+                write!(
+                    f,
+                    "{} {}",
+                    monster!(*monster).name,
+                    Effect::EggInArea(*area)
+                )
+            }
+            Condition::ShiftedEggInArea(monster, area, shift) => write!(
+                f,
+                "{} {}",
+                monster_value(*monster),
+                Effect::ShiftedEggInArea(*area, *shift)
+            ),
+            Condition::ShiftedArmy(monster, shift) => write!(
+                f,
+                "{} {}",
+                monster_value(*monster),
+                Effect::ShiftedArmy(*shift)
+            ),
+            Condition::RelicInArea(relic, area) => {
+                write!(f, "{} {}", relic!(*relic).name, Effect::InArea(*area))
+            }
+            Condition::RelicInScene(relic, scene, chest) => write!(
+                f,
+                "{} {}",
+                relic!(*relic).name,
+                Effect::InScene(*scene, *chest)
+            ),
+            Condition::Replacement(monster, replacement) => write!(
+                f,
+                "{} {}",
+                monster!(*monster).name,
+                Effect::Replacement(*replacement)
+            ),
+        }
+    }
+}
+
+/// Returns a monster, or any monster if `None`.
+fn monster_value(monster: Option<u32>) -> Value {
+    monster.map_or(Value::AnyMonster, Value::Monster)
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    /// Parses a condition written as `<monster or relic>:<effect>[:<arguments>]`.
+    ///
+    /// Names are case-insensitive and may omit spaces, e.g. `Vaero:starter`,
+    /// `Tanuki:egg:MountainPath:light`, `Koi:replacement:Vaero`, `"Sun Ring:area:Blue Caves"`
+    /// or `"Sun Ring:scene:MountainPath_West2:19"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Condition::from(parse_value_effect(s)?) {
+            Condition::Invalid(err) => Err(err),
+            condition => Ok(condition),
+        }
+    }
+}
+
+/// Parses the value and effect of a condition written as `<value>:<effect>[:<arguments>]`.
+///
+/// The value is a monster or relic name, `any monster`, or `any <monster type or explore ability>`
+/// for a group of monsters, e.g. `any Dragon:starter` or `"any Mount:egg:Blue Caves"`.
+pub fn parse_value_effect(s: &str) -> Result<(Value, Effect), String> {
+    let mut parts = s.split(':');
+
+    let name = parts.next().unwrap_or_default().trim();
+    let effect = parts
+        .next()
+        .ok_or(format!("Missing effect in condition \"{s}\"."))?;
+    let arguments = parts.collect::<Vec<&str>>();
+
+    let value = match name.split_once(' ') {
+        Some((any, group)) if any.eq_ignore_ascii_case("any") && normalize(group) == "monster" => {
+            Value::AnyMonster
+        }
+        Some((any, group)) if any.eq_ignore_ascii_case("any") => {
+            if let Some(monster_type) = find_by_name(&GAME_DATA.monster_types, group, |x| &x.name) {
+                Value::MonsterType(monster_type)
+            } else if let Some(ability) = find_by_name(&GAME_DATA.abilities, group, |x| &x.name) {
+                Value::Ability(ability)
+            } else {
+                return Err(format!(
+                    "Unknown monster type or explore ability \"{group}\"."
+                ));
+            }
+        }
+        _ => {
+            if let Some(monster) = find_by_name(&GAME_DATA.monsters, name, |x| &x.name) {
+                Value::Monster(monster)
+            } else if let Some(relic) = find_by_name(&GAME_DATA.relics, name, |x| &x.name) {
+                Value::Relic(relic)
+            } else {
+                return Err(format!("Unknown monster or relic \"{name}\"."));
+            }
+        }
+    };
+
+    let effect = match (normalize(effect).as_str(), arguments.as_slice()) {
+        ("available", []) => Effect::Available,
+        ("chest", []) => Effect::BraveryChest,
+        ("familiar", []) => Effect::Familiar,
+        ("starter", []) => Effect::Starter,
+        ("swimming", []) => Effect::Swimming,
+        ("bex", []) => Effect::Bex,
+        ("cryomancer", []) => Effect::Cryomancer,
+        ("cryomancerrequired", []) => Effect::CryomancerRequired,
+        ("endoftime", []) => Effect::EndOfTime,
+        ("army", []) => Effect::Army,
+        ("army", [shift]) => Effect::ShiftedArmy(parse_shift(shift)?),
+        ("area", [area]) => Effect::InArea(parse_area(area)?),
+        ("scene", [scene]) => Effect::InScene(parse_scene(scene)?, None),
+        ("scene", [scene, chest]) => {
+            let scene = parse_scene(scene)?;
+            Effect::InScene(scene, Some(parse_chest(scene, chest)?))
+        }
+        ("egg", [area]) => Effect::EggInArea(parse_area(area)?),
+        ("egg", [area, shift]) => Effect::ShiftedEggInArea(parse_area(area)?, parse_shift(shift)?),
+        ("replacement", [monster]) => Effect::Replacement(
+            find_by_name(&GAME_DATA.monsters, monster, |x| &x.name)
+                .ok_or(format!("Unknown monster \"{monster}\"."))?,
+        ),
+        _ => return Err(format!("Unknown effect in condition \"{s}\".")),
+    };
+
+    Ok((value, effect))
+}
+
+/// Returns the index of the first item whose name matches, ignoring case, spaces and punctuation.
+fn find_by_name<T>(items: &[T], name: &str, key: fn(&T) -> &str) -> Option<u32> {
+    let name = normalize(name);
+
+    items
+        .iter()
+        .position(|x| normalize(key(x)) == name)
+        .map(|x| x as u32)
+}
+
+fn parse_area(name: &str) -> Result<u32, String> {
+    find_by_name(&GAME_DATA.areas, name, |x| &x.name).ok_or(format!("Unknown area \"{name}\"."))
+}
+
+/// Returns the ID of a scene from its name, e.g. `MountainPath_West1`.
+fn parse_scene(name: &str) -> Result<u32, String> {
+    find_by_name(&GAME_DATA.area_data, name, |x| &x.scene_name)
+        .map(|x| GAME_DATA.area_data[x as usize].scene_id)
+        .ok_or(format!("Unknown scene \"{name}\"."))
+}
+
+fn parse_chest(scene: u32, chest: &str) -> Result<u32, String> {
+    chest
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|x| scene_data(scene).chests.contains(x))
+        .ok_or(format!("Unknown chest \"{chest}\" in this scene."))
+}
+
+fn parse_shift(name: &str) -> Result<Shift, String> {
+    match normalize(name).as_str() {
+        "normal" => Ok(Shift::Normal),
+        "light" => Ok(Shift::Light),
+        "dark" => Ok(Shift::Dark),
+        _ => Err(format!("Unknown shift \"{name}\".")),
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|x| x.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl Condition {
+    /// Returns an error message if this condition cannot be used with the selected game modes.
+    pub fn validate(&self, is_randomizer: bool, is_bravery: bool, is_relic: bool) -> Option<&str> {
+        if let Condition::Invalid(err) = self {
+            return Some(err.as_str());
+        }
+
+        if let Condition::MonsterAvailable(_) = self {
+            if is_randomizer && !is_bravery {
+                return Some("Every monster is available in Randomizer mode.");
+            }
+        }
+
+        if let Condition::BraveryChest(id) = self {
+            if *id <= 3 {
+                return Some("Spectral familiars cannot be obtained in Bravery area chests.");
+            }
+        }
+
+        if let Condition::Swimming(id) = self {
+            if !GAME_DATA.swimming_monsters.contains(id) {
+                return Some("This monster cannot be given at the Sun Palace.");
+            }
+        }
+
+        if let Condition::Bex(id) = self {
+            if *id <= 3 {
+                return Some("Spectral familiars cannot be obtained from Bex.");
+            }
+        }
+
+        if let Condition::CryomancerRequired(id) = self {
+            if *id <= 3 {
+                return Some("Spectral familiars cannot be wanted by the Cryomancer.");
+            }
+        }
+
+        if let Condition::EggInArea(id, _) | Condition::ShiftedEggInArea(Some(id), _, _) = self {
+            if *id <= 3 {
+                return Some("Spectral familiars cannot be obtained in Bravery area chests.");
+            }
+        }
+
+        if let Condition::MonsterInArea(id, _) = self {
+            if *id <= 3 {
+                return Some("Spectral familiars cannot be found in any area.");
+            }
+        }
+
+        if let Condition::Replacement(monster, replacement) = self {
+            if *monster <= 3 {
+                return Some(
+                    "Spectral familiars cannot be replaced by any monster. You may be looking for the \"Eternity's End\" condition.",
+                );
+            }
+
+            if *monster == *replacement {
+                return Some("Cannot replace a monster with itself.");
+            }
+        }
+
+        self.required_mode(is_randomizer, is_bravery, is_relic)
+    }
+
+    /// Returns an error message if a game mode this condition relies on is not selected.
+    fn required_mode(&self, is_randomizer: bool, is_bravery: bool, is_relic: bool) -> Option<&str> {
+        match self {
+            Condition::RelicAvailable(_)
+            | Condition::RelicInArea(_, _)
+            | Condition::RelicInScene(_, _, _)
+                if !is_relic =>
+            {
+                Some("This condition requires the Relics of Chaos mode.")
+            }
+            Condition::MonsterInArea(_, _) | Condition::Replacement(_, _) if !is_randomizer => {
+                Some("This condition requires the Randomizer mode.")
+            }
+            Condition::MonsterAvailable(_)
+            | Condition::BraveryChest(_)
+            | Condition::Familiar(_)
+            | Condition::Starter(_)
+            | Condition::Swimming(_)
+            | Condition::Bex(_)
+            | Condition::CryomancerRequired(_)
+            | Condition::Cryomancer(_)
+            | Condition::Army(_)
+            | Condition::EndOfTime(_)
+            | Condition::EggInArea(_, _)
+            | Condition::ShiftedEggInArea(_, _, _)
+            | Condition::ShiftedArmy(_, _)
+                if !is_bravery =>
+            {
+                Some("This condition requires the Bravery mode.")
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the SQL for this condition, pushing the values it is compared to in `params`.
+    ///
+    /// Column names only ever come from the known table columns, never from the condition.
+    pub fn to_sql(&self, params: &mut Vec<u32>) -> String {
+        match self {
+            Condition::Invalid(_) => String::from(""),
+            Condition::MonsterAvailable(id) => in_columns(*id, &BRAVERY_COLUMNS, params),
+            Condition::RelicAvailable(relic) => {
+                in_columns(relic!(*relic).id, &RELIC_COLUMNS, params)
+            }
+            Condition::BraveryChest(id) => {
+                in_columns(*id, &BRAVERY_COLUMNS[BRAVERY_AREAS..], params)
+            }
+            Condition::Familiar(id) => in_columns(*id, &BRAVERY_COLUMNS[0..1], params),
+            Condition::Starter(id) => in_columns(*id, &BRAVERY_COLUMNS[1..3], params),
+            Condition::Swimming(id) => in_columns(*id, &BRAVERY_COLUMNS[3..4], params),
+            Condition::Bex(id) => in_columns(*id, &BRAVERY_COLUMNS[4..5], params),
+            Condition::Cryomancer(id) => in_columns(*id, &BRAVERY_COLUMNS[5..6], params),
+            Condition::CryomancerRequired(id) => in_columns(*id, &BRAVERY_COLUMNS[6..7], params),
+            Condition::EndOfTime(id) => in_columns(*id, &BRAVERY_COLUMNS[7..10], params),
+            Condition::Army(id) => in_columns(*id, &BRAVERY_COLUMNS[10..17], params),
+            Condition::MonsterInArea(monster, area) => in_columns(
+                *monster,
+                &GAME_DATA.areas[*area as usize]
+                    .wild_monsters
+                    .iter()
+                    .map(|x| RANDOMIZER_COLUMNS[*x as usize - 4].clone())
+                    .collect::<Vec<String>>(),
+                params,
+            ),
+            Condition::EggInArea(monster, area) => in_columns(
+                *monster,
+                &BRAVERY_COLUMNS[BRAVERY_AREAS + *area as usize..][..1],
+                params,
+            ),
+            Condition::ShiftedEggInArea(monster, area, shift) => match monster {
+                Some(monster) => {
+                    let egg = in_columns(
+                        *monster,
+                        &BRAVERY_COLUMNS[BRAVERY_AREAS + *area as usize..][..1],
+                        params,
+                    );
+
+                    format!("{egg} AND {}", shift_sql(*area, *shift, params))
+                }
+                None => shift_sql(*area, *shift, params),
+            },
+            Condition::ShiftedArmy(monster, shift) => (0..7)
+                .map(|i| {
+                    let column = &BRAVERY_COLUMNS[10 + i..][..1];
+                    let army = match monster {
+                        Some(monster) => in_columns(*monster, column, params),
+                        None => format!("{} IS NOT NULL", column[0]),
+                    };
+
+                    format!("({army} AND {})", shift_sql(i as u32, *shift, params))
+                })
+                .collect::<Vec<String>>()
+                .join(" OR "),
+            Condition::RelicInArea(relic, area) => in_columns(
+                relic!(*relic).id,
+                &RELIC_COLUMNS[*area as usize..][..1],
+                params,
+            ),
+            Condition::RelicInScene(relic, scene, chest) => {
+                let area = scene_data(*scene).area_id as usize;
+                let relic = in_columns(relic!(*relic).id, &RELIC_COLUMNS[area..][..1], params);
+
+                params.push(*scene);
+                let mut sql = format!("{relic} AND {} = ?", RELIC_SCENE_COLUMNS[area]);
+
+                if let Some(chest) = chest {
+                    params.push(*chest);
+                    sql += &format!(" AND {} = ?", RELIC_CHEST_COLUMNS[area]);
+                }
+
+                sql
+            }
+            Condition::Replacement(monster, replacement) => in_columns(
+                *replacement,
+                &RANDOMIZER_COLUMNS[*monster as usize - 4..][..1],
+                params,
+            ),
+        }
+    }
+
+    /// Returns true if a generated game matches this condition.
+    ///
+    /// The game is checked against the rows it would be stored as, the same way as [`Condition::to_sql`],
+    /// so that conditions on data the game does not have never match.
+    pub fn matches(&self, game: &Game) -> bool {
+        let randomizer = |check: &dyn Fn(&[Option<i64>]) -> bool| {
+            game.mapping
+                .as_deref()
+                .is_some_and(|x| check(&db::randomizer_values(x)))
+        };
+        let bravery = |check: &dyn Fn(&[Option<i64>]) -> bool| {
+            game.bravery_data
+                .as_ref()
+                .is_some_and(|x| check(&db::bravery_values(x)))
+        };
+        let relic = |check: &dyn Fn(&[Option<i64>]) -> bool| {
+            game.relics
+                .as_ref()
+                .is_some_and(|x| check(&db::relic_values(x)))
+        };
+
+        match self {
+            Condition::Invalid(_) => false,
+            Condition::MonsterAvailable(id) => {
+                bravery(&|row| in_values(*id, &row[..BRAVERY_COLUMNS.len()]))
+            }
+            Condition::RelicAvailable(relic_id) => {
+                relic(&|row| in_values(relic!(*relic_id).id, &row[..RELIC_COLUMNS.len()]))
+            }
+            Condition::BraveryChest(id) => {
+                bravery(&|row| in_values(*id, &row[BRAVERY_AREAS..BRAVERY_COLUMNS.len()]))
+            }
+            Condition::Familiar(id) => bravery(&|row| in_values(*id, &row[0..1])),
+            Condition::Starter(id) => bravery(&|row| in_values(*id, &row[1..3])),
+            Condition::Swimming(id) => bravery(&|row| in_values(*id, &row[3..4])),
+            Condition::Bex(id) => bravery(&|row| in_values(*id, &row[4..5])),
+            Condition::Cryomancer(id) => bravery(&|row| in_values(*id, &row[5..6])),
+            Condition::CryomancerRequired(id) => bravery(&|row| in_values(*id, &row[6..7])),
+            Condition::EndOfTime(id) => bravery(&|row| in_values(*id, &row[7..10])),
+            Condition::Army(id) => bravery(&|row| in_values(*id, &row[10..17])),
+            Condition::MonsterInArea(monster, area) => randomizer(&|row| {
+                GAME_DATA.areas[*area as usize]
+                    .wild_monsters
+                    .iter()
+                    .any(|x| row[*x as usize - 4] == Some(*monster as i64))
+            }),
+            Condition::EggInArea(monster, area) => {
+                bravery(&|row| in_values(*monster, &row[BRAVERY_AREAS + *area as usize..][..1]))
+            }
+            Condition::ShiftedEggInArea(monster, area, shift) => bravery(&|row| {
+                let egg = &row[BRAVERY_AREAS + *area as usize..][..1];
+
+                monster.is_none_or(|x| in_values(x, egg)) && has_shift(row, *area, *shift)
+            }),
+            Condition::ShiftedArmy(monster, shift) => bravery(&|row| {
+                (0..7).any(|i| {
+                    let army = match monster {
+                        Some(monster) => in_values(*monster, &row[10 + i..][..1]),
+                        None => row[10 + i].is_some(),
+                    };
+
+                    army && has_shift(row, i as u32, *shift)
+                })
+            }),
+            Condition::RelicInArea(relic_id, area) => {
+                relic(&|row| in_values(relic!(*relic_id).id, &row[*area as usize..][..1]))
+            }
+            Condition::RelicInScene(relic_id, scene, chest) => relic(&|row| {
+                let area = scene_data(*scene).area_id as usize;
+                // The scene and chest of each area's relic follow the relics
+                let [scene_id, chest_id] = [0, 1].map(|x| row[RELIC_COLUMNS.len() + area * 2 + x]);
+
+                in_values(relic!(*relic_id).id, &row[area..][..1])
+                    && scene_id == Some(*scene as i64)
+                    && chest.is_none_or(|x| chest_id == Some(x as i64))
+            }),
+            Condition::Replacement(monster, replacement) => {
+                randomizer(&|row| in_values(*replacement, &row[*monster as usize - 4..][..1]))
+            }
+        }
+    }
+}
+
+/// Returns the scene with this ID, which always comes from the game data.
+fn scene_data(scene: u32) -> &'static AreaData {
+    GAME_DATA
+        .area_data
+        .iter()
+        .find(|x| x.scene_id == scene)
+        .unwrap()
+}
+
+/// Returns the SQL checking if the egg at this position of the area or army eggs has a shift.
+fn shift_sql(position: u32, shift: Shift, params: &mut Vec<u32>) -> String {
+    let positions = BraveryData::get_shift_positions(shift);
+
+    params.push(position);
+    params.push(BraveryData::SHIFT_DATA.len() as u32);
+    params.extend(&positions);
+
+    format!(
+        "(ShiftOffset + ?) % ? IN ({})",
+        vec!["?"; positions.len()].join(",")
+    )
+}
+
+/// Returns true if the egg at this position of the area or army eggs of a `Bravery` row
+/// has a shift, like [`shift_sql`].
+fn has_shift(row: &[Option<i64>], position: u32, shift: Shift) -> bool {
+    // The shift offset follows the columns of the conditions
+    let offset = row[BRAVERY_COLUMNS.len()].unwrap() as u32;
+    let len = BraveryData::SHIFT_DATA.len() as u32;
+
+    BraveryData::get_shift_positions(shift).contains(&((offset + position) % len))
+}
+
+/// Returns true if a value is in any of the values of a row, like [`in_columns`].
+fn in_values(value: u32, values: &[Option<i64>]) -> bool {
+    values.contains(&Some(value as i64))
+}
+
+/// Returns the SQL checking if a value is in any of the columns.
+fn in_columns(value: u32, columns: &[String], params: &mut Vec<u32>) -> String {
+    params.push(value);
+
+    format!("? IN ({})", columns.join(","))
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+
+use crate::{
+    data::{
+        macros::{area, monster},
+        GAME_DATA,
+    },
+    structs::modes::bravery::Shift,
+};
+
+/// Egg shifts that can be searched for in the seed finder.
+const SHIFTS: [Shift; 2] = [Shift::Light, Shift::Dark];
+
+#[derive(Clone, Copy)]
+pub enum Effect {
+    None,
+    Available,
+    BraveryChest,
+    Familiar,
+    Starter,
+    Swimming,
+    Bex,
+    Cryomancer,
+    CryomancerRequired,
+    EndOfTime,
+    Army,
+    InArea(u32),
+    /// A scene, and optionally one of its chests.
+    InScene(u32, Option<u32>),
+    EggInArea(u32),
+    ShiftedEggInArea(u32, Shift),
+    ShiftedArmy(Shift),
+    Replacement(u32),
+}
+
+impl From<u32> for Effect {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Effect::Available,
+            1 => Effect::BraveryChest,
+            2 => Effect::Familiar,
+            3 => Effect::Starter,
+            4 => Effect::Swimming,
+            5 => Effect::Bex,
+            6 => Effect::Cryomancer,
+            7 => Effect::CryomancerRequired,
+            8 => Effect::EndOfTime,
+            9 => Effect::Army,
+            10..=22 => Effect::EggInArea(value - 10),
+            23..=48 => {
+                Effect::ShiftedEggInArea((value - 23) / 2, SHIFTS[(value as usize - 23) % 2])
+            }
+            49..=50 => Effect::ShiftedArmy(SHIFTS[value as usize - 49]),
+            51..=63 => Effect::InArea(value - 51),
+            _ => Effect::Replacement(value - 64),
+        }
+    }
+}
+
+impl From<(u32, bool, bool, bool)> for Effect {
+    fn from((value, is_randomizer, is_bravery, is_relic): (u32, bool, bool, bool)) -> Self {
+        if is_bravery {
+            // Bravery (+ Randomizer) (+ Relic)
+            match value {
+                0 => Effect::Available,
+                1 => Effect::BraveryChest,
+                2 => Effect::Familiar,
+                3 => Effect::Starter,
+                4 => Effect::Swimming,
+                5 => Effect::Bex,
+                6 => Effect::Cryomancer,
+                7 => Effect::CryomancerRequired,
+                8 => Effect::EndOfTime,
+                9 => Effect::Army,
+                10..=22 => Effect::EggInArea(value - 10),
+                23..=48 => {
+                    Effect::ShiftedEggInArea((value - 23) / 2, SHIFTS[(value as usize - 23) % 2])
+                }
+                49..=50 => Effect::ShiftedArmy(SHIFTS[value as usize - 49]),
+                _ => {
+                    // Bard is not included as it is an hard-coded reward
+                    let replacements = GAME_DATA.monsters.len() as u32 - 1;
+
+                    if is_randomizer {
+                        match value {
+                            51..=63 => Effect::InArea(value - 51),
+                            _ if value < 64 + replacements => Effect::Replacement(value - 64),
+                            _ => scene(value - 64 - replacements),
+                        }
+                    } else if is_relic {
+                        match value {
+                            51..=63 => Effect::InArea(value - 51),
+                            _ => scene(value - 64),
+                        }
+                    } else {
+                        Effect::None
+                    }
+                }
+            }
+        } else if is_randomizer {
+            if is_relic {
+                // Randomizer + Relic
+                let replacements = GAME_DATA.monsters.len() as u32;
+
+                match value {
+                    0 => Effect::Available,
+                    1..=13 => Effect::InArea(value - 1),
+                    _ if value < 14 + replacements => Effect::Replacement(value - 14),
+                    _ => scene(value - 14 - replacements),
+                }
+            } else {
+                // Randomizer
+                match value {
+                    0..=12 => Effect::InArea(value),
+                    _ => Effect::Replacement(value - 13),
+                }
+            }
+        } else if is_relic {
+            match value {
+                0 => Effect::Available,
+                _ => scene(value - 1),
+            }
+        } else {
+            Effect::None
+        }
+    }
+}
+
+/// Returns the effect for the scene at this index of the game data.
+fn scene(index: u32) -> Effect {
+    GAME_DATA
+        .area_data
+        .get(index as usize)
+        .map_or(Effect::None, |x| Effect::InScene(x.scene_id, None))
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Effect::None => write!(f, "has no effect"),
+            Effect::Available => write!(f, "is available"),
+            Effect::BraveryChest => write!(f, "is in a Bravery area chest"),
+            Effect::Familiar => write!(f, "is your spectral familiar"),
+            Effect::Starter => write!(f, "is a starter"),
+            Effect::Swimming => write!(f, "is given at the Sun Palace"),
+            Effect::Bex => write!(f, "is given by Bex"),
+            Effect::Cryomancer => write!(f, "is given by the Cryomancer"),
+            Effect::CryomancerRequired => write!(f, "is wanted by the Cryomancer"),
+            Effect::EndOfTime => write!(f, "is in Eternity's End"),
+            Effect::Army => write!(f, "is in the Bravery Monster Army"),
+            Effect::EggInArea(value) => write!(f, "egg is in {}", area!(*value).name),
+            Effect::ShiftedEggInArea(value, shift) => {
+                write!(f, "egg is in {} ({shift})", area!(*value).name)
+            }
+            Effect::ShiftedArmy(shift) => {
+                write!(f, "is a {shift} egg in the Bravery Monster Army")
+            }
+            Effect::InArea(value) => write!(f, "is in {}", area!(*value).name),
+            Effect::InScene(scene, chest) => {
+                let name = GAME_DATA
+                    .area_data
+                    .iter()
+                    .find(|x| x.scene_id == *scene)
+                    .map_or("", |x| &x.scene_name);
+
+                match chest {
+                    Some(chest) => write!(f, "is in {name} (chest {chest})"),
+                    None => write!(f, "is in {name}"),
+                }
+            }
+            Effect::Replacement(value) => write!(f, "=> {}", monster!(*value).name),
+        }
+    }
+}
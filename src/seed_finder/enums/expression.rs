@@ -0,0 +1,276 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    seed_finder::enums::{
+        condition::{self, Condition},
+        effect::Effect,
+        value::Value,
+    },
+    structs::game::Game,
+};
+
+/// A boolean expression over conditions for the seed finder.
+#[derive(Clone)]
+pub enum Expression {
+    Condition(Condition),
+    /// A condition on a group of monsters, matching if any of the expanded conditions does.
+    Any(String, Vec<Expression>),
+    And(Vec<Expression>),
+    Or(Vec<Expression>),
+    Not(Box<Expression>),
+}
+
+impl From<Condition> for Expression {
+    fn from(condition: Condition) -> Self {
+        Expression::Condition(condition)
+    }
+}
+
+impl From<(Value, Effect)> for Expression {
+    /// Creates a condition, expanding groups of monsters against the game data.
+    fn from((value, effect): (Value, Effect)) -> Self {
+        let Some(monsters) = value.group_monsters() else {
+            return Expression::Condition(Condition::from((value, effect)));
+        };
+
+        // Leave out monsters that can never match, e.g. spectral familiars as eggs
+        let list = monsters
+            .into_iter()
+            .map(|x| Condition::from((Value::Monster(x), effect)))
+            .filter(|x| x.validate(true, true, true).is_none())
+            .map(Expression::Condition)
+            .collect::<Vec<Expression>>();
+
+        if list.is_empty() {
+            return Expression::Condition(Condition::Invalid(format!(
+                "{value} cannot match this condition."
+            )));
+        }
+
+        Expression::Any(format!("{value} {effect}"), list)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Condition(condition) => write!(f, "{condition}"),
+            Expression::Any(name, _) => write!(f, "{name}"),
+            Expression::And(list) => write_list(f, list, "AND"),
+            Expression::Or(list) => write_list(f, list, "OR"),
+            Expression::Not(expression) => match **expression {
+                Expression::Condition(_) | Expression::Any(_, _) | Expression::Not(_) => {
+                    write!(f, "NOT {expression}")
+                }
+                _ => write!(f, "NOT ({expression})"),
+            },
+        }
+    }
+}
+
+/// Writes the expressions separated by an operator, grouping nested `AND`/`OR` expressions.
+fn write_list(f: &mut fmt::Formatter, list: &[Expression], operator: &str) -> fmt::Result {
+    for (i, expression) in list.iter().enumerate() {
+        if i > 0 {
+            write!(f, " {operator} ")?;
+        }
+
+        match expression {
+            Expression::And(_) | Expression::Or(_) => write!(f, "({expression})")?,
+            _ => write!(f, "{expression}")?,
+        }
+    }
+
+    Ok(())
+}
+
+impl FromStr for Expression {
+    type Err = String;
+
+    /// Parses conditions combined with `&` (and), `|` (or), `!` (not) and parentheses.
+    ///
+    /// `&` has precedence over `|`, e.g. `Vaero:starter | Kanko:starter & !Tanuki:available`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s),
+            position: 0,
+        };
+
+        let expression = parser.parse_or()?;
+
+        match parser.tokens.get(parser.position) {
+            None => Ok(expression),
+            Some(_) => Err(format!("Invalid expression \"{s}\".")),
+        }
+    }
+}
+
+enum Token<'a> {
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    Condition(&'a str),
+}
+
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        let token = match c {
+            '&' => Token::And,
+            '|' => Token::Or,
+            '!' => Token::Not,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ => continue,
+        };
+
+        if !s[start..i].trim().is_empty() {
+            tokens.push(Token::Condition(s[start..i].trim()));
+        }
+
+        tokens.push(token);
+        start = i + 1;
+    }
+
+    if !s[start..].trim().is_empty() {
+        tokens.push(Token::Condition(s[start..].trim()));
+    }
+
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn parse_or(&mut self) -> Result<Expression, String> {
+        let mut list = vec![self.parse_and()?];
+
+        while let Some(Token::Or) = self.tokens.get(self.position) {
+            self.position += 1;
+            list.push(self.parse_and()?);
+        }
+
+        Ok(if list.len() == 1 {
+            list.remove(0)
+        } else {
+            Expression::Or(list)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, String> {
+        let mut list = vec![self.parse_not()?];
+
+        while let Some(Token::And) = self.tokens.get(self.position) {
+            self.position += 1;
+            list.push(self.parse_not()?);
+        }
+
+        Ok(if list.len() == 1 {
+            list.remove(0)
+        } else {
+            Expression::And(list)
+        })
+    }
+
+    fn parse_not(&mut self) -> Result<Expression, String> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+
+        match token {
+            Some(Token::Not) => Ok(Expression::Not(Box::new(self.parse_not()?))),
+            Some(Token::Open) => {
+                let expression = self.parse_or()?;
+
+                match self.tokens.get(self.position) {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Ok(expression)
+                    }
+                    _ => Err(String::from("Missing \")\" in expression.")),
+                }
+            }
+            Some(Token::Condition(condition)) => {
+                match Expression::from(condition::parse_value_effect(condition)?) {
+                    Expression::Condition(Condition::Invalid(err)) => Err(err),
+                    expression => Ok(expression),
+                }
+            }
+            _ => Err(String::from("Missing condition in expression.")),
+        }
+    }
+}
+
+impl Expression {
+    /// Returns an error message if a condition of this expression
+    /// cannot be used with the selected game modes.
+    pub fn validate(&self, is_randomizer: bool, is_bravery: bool, is_relic: bool) -> Option<&str> {
+        match self {
+            Expression::Condition(condition) => {
+                condition.validate(is_randomizer, is_bravery, is_relic)
+            }
+            Expression::Any(_, list) | Expression::And(list) | Expression::Or(list) => list
+                .iter()
+                .find_map(|x| x.validate(is_randomizer, is_bravery, is_relic)),
+            Expression::Not(expression) => expression.validate(is_randomizer, is_bravery, is_relic),
+        }
+    }
+
+    /// Returns this expression as a negation, or removes it if it is already negated.
+    pub fn negate(self) -> Expression {
+        match self {
+            Expression::Not(expression) => *expression,
+            expression => Expression::Not(Box::new(expression)),
+        }
+    }
+
+    /// Returns this expression combined with another one by `OR`.
+    pub fn or(self, other: Expression) -> Expression {
+        match self {
+            Expression::Or(mut list) => {
+                list.push(other);
+                Expression::Or(list)
+            }
+            expression => Expression::Or(vec![expression, other]),
+        }
+    }
+
+    /// Returns true if a generated game matches this expression, as it would in the SQL query.
+    pub fn matches(&self, game: &Game) -> bool {
+        match self {
+            Expression::Condition(condition) => condition.matches(game),
+            Expression::And(list) => list.iter().all(|x| x.matches(game)),
+            Expression::Any(_, list) | Expression::Or(list) => list.iter().any(|x| x.matches(game)),
+            Expression::Not(expression) => !expression.matches(game),
+        }
+    }
+
+    /// Returns the SQL for this expression, pushing the values it is compared to in `params`.
+    pub fn to_sql(&self, params: &mut Vec<u32>) -> String {
+        match self {
+            Expression::Condition(condition) => condition.to_sql(params),
+            Expression::And(list) if list.is_empty() => String::from("1"),
+            Expression::Or(list) if list.is_empty() => String::from("0"),
+            Expression::And(list) => join_sql(list, " AND ", params),
+            Expression::Any(_, list) | Expression::Or(list) => join_sql(list, " OR ", params),
+            // Columns such as `Army7` can be NULL, which makes `IN` return NULL instead of false
+            // and `NOT` would then discard the row
+            Expression::Not(expression) => {
+                format!("NOT IFNULL(({}), 0)", expression.to_sql(params))
+            }
+        }
+    }
+}
+
+fn join_sql(list: &[Expression], operator: &str, params: &mut Vec<u32>) -> String {
+    list.iter()
+        .map(|x| format!("({})", x.to_sql(params)))
+        .collect::<Vec<String>>()
+        .join(operator)
+}
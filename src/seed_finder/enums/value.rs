@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::{
+    data::{
+        macros::{ability, monster, relic},
+        GAME_DATA,
+    },
+    structs::monster::EMonster,
+};
+
+#[derive(Clone, Copy)]
+pub enum Value {
+    Monster(u32),
+    Relic(u32),
+    /// Any monster, for conditions that do not need a specific one.
+    AnyMonster,
+    /// Any monster of this type.
+    MonsterType(u32),
+    /// Any monster with this explore ability.
+    Ability(u32),
+}
+
+impl From<(u32, bool, bool)> for Value {
+    fn from((id, is_randomizer, is_bravery): (u32, bool, bool)) -> Self {
+        // Skip the `None` monster type
+        let types = GAME_DATA.monster_types.len() as u32 - 1;
+        let abilities = GAME_DATA.abilities.len() as u32;
+
+        if is_randomizer || is_bravery {
+            match id {
+                // Do not include Bard as it is an hard-coded reward
+                0..=109 => Value::Monster(id),
+                110 => Value::AnyMonster,
+                _ if id < 111 + types => Value::MonsterType(id - 111 + 1),
+                _ if id < 111 + types + abilities => Value::Ability(id - 111 - types),
+                _ => Value::Relic(id - 111 - types - abilities),
+            }
+        } else {
+            Value::Relic(id)
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Monster(monster) => write!(f, "{}", monster!(*monster).name),
+            Value::Relic(relic) => write!(f, "{}", relic!(*relic).name),
+            Value::AnyMonster => write!(f, "Any monster"),
+            Value::MonsterType(monster_type) => write!(
+                f,
+                "Any {} monster",
+                GAME_DATA.monster_types[*monster_type as usize].name
+            ),
+            Value::Ability(ability) => {
+                write!(f, "Any {} monster", ability_name(&ability!(*ability).name))
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Returns every monster of a type or with an explore ability,
+    /// or `None` for a single monster or relic.
+    pub fn group_monsters(&self) -> Option<Vec<u32>> {
+        let monsters = GAME_DATA
+            .monsters
+            .iter()
+            // Do not include Bard as it is an hard-coded reward
+            .filter(|x| x.id != EMonster::Bard as u32);
+
+        match self {
+            Value::Monster(_) | Value::Relic(_) | Value::AnyMonster => None,
+            Value::MonsterType(monster_type) => Some(
+                monsters
+                    .filter(|x| x.monster_types.contains(monster_type))
+                    .map(|x| x.id)
+                    .collect(),
+            ),
+            Value::Ability(ability) => Some(
+                monsters
+                    .filter(|x| {
+                        ability!(*ability)
+                            .explore_actions
+                            .contains(&x.explore_action)
+                    })
+                    .map(|x| x.id)
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Splits an explore ability's name into words, e.g. `BreakWall` into `Break Wall`.
+fn ability_name(name: &str) -> String {
+    let mut words = String::new();
+
+    for (i, c) in name.char_indices() {
+        if i > 0 && c.is_uppercase() {
+            words.push(' ');
+        }
+
+        words.push(c);
+    }
+
+    words
+}
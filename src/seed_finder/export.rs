@@ -0,0 +1,402 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{
+    data::{macros::monster, GAME_DATA},
+    seed_finder::{
+        backend::{Backend, Error, Result, RowReader},
+        db,
+        score::ScoredSeed,
+        search,
+    },
+    structs::{game::Game, random::TracedCall},
+};
+
+/// Number of `Bravery` columns exported, from the familiar to the last End of Time monster.
+const BRAVERY_KEY_COLUMNS: usize = 10;
+
+/// Format of an exported file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// Returns the format of a file from its extension.
+    pub fn from_path(path: &Path) -> Result<Format> {
+        let extension = path
+            .extension()
+            .and_then(|x| x.to_str())
+            .map(|x| x.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("json") => Ok(Format::Json),
+            _ => Err(Error::Invalid(format!(
+                "Cannot export to \"{}\": use a .csv or .json file.",
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// Returns the path of a file exported without choosing one, in the `exports` directory
+/// of the data directory.
+pub fn default_path(name: &str, format: Format) -> PathBuf {
+    db::data_dir()
+        .join("exports")
+        .join(format!("{name}.{}", format.extension()))
+}
+
+/// A column of the seeds database exported along with the seeds.
+struct Column {
+    name: String,
+    /// Index of the table in `TABLES`.
+    table: usize,
+    /// Index of the column after the id.
+    index: usize,
+    is_relic: bool,
+}
+
+const TABLES: [&str; 3] = ["Randomizer", "Bravery", "Relic"];
+
+/// Exports seeds along with the key columns of their games for the selected modes, by name:
+/// the Randomizer replacements, the Bravery familiar, starters and monsters of note,
+/// and the relic of each area.
+///
+/// Rows are read from the database if one is given, or generated otherwise.
+/// In CSV, each seed is a line; in JSON, an object with the same fields.
+#[allow(clippy::too_many_arguments)]
+pub fn export_seeds(
+    path: &Path,
+    format: Format,
+    seeds: &[ScoredSeed],
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    backend: Option<&dyn Backend>,
+) -> Result<()> {
+    let columns = columns(is_randomizer, is_bravery, is_relic)?;
+    let mut reader = backend.map(|x| x.reader()).transpose()?;
+
+    let header = ["Seed", "Score", "Preferred"]
+        .into_iter()
+        .map(String::from)
+        .chain(columns.iter().map(|x| x.name.clone()))
+        .collect::<Vec<String>>();
+
+    let records = seeds
+        .iter()
+        .map(|seed| {
+            let rows = match reader.as_mut() {
+                Some(reader) => read_rows(
+                    reader.as_mut(),
+                    seed.seed,
+                    is_randomizer,
+                    is_bravery,
+                    is_relic,
+                )?,
+                None => generate_rows(seed.seed, is_randomizer, is_bravery, is_relic),
+            };
+
+            Ok(record(seed, &columns, &rows))
+        })
+        .collect::<Result<Vec<Vec<Value>>>>()?;
+
+    let mut file = create(path)?;
+
+    match format {
+        Format::Csv => write_csv(&mut file, &header, &records)?,
+        Format::Json => {
+            let records = records
+                .iter()
+                .map(|x| Record(&header, x))
+                .collect::<Vec<Record>>();
+
+            serde_json::to_writer_pretty(&mut file, &records).map_err(io::Error::from)?;
+        }
+    }
+
+    Ok(file.flush()?)
+}
+
+/// Exports all data of a game.
+///
+/// In JSON, this is the game as the `seed` command prints it.
+/// In CSV, each value is a line along with the path of its field, e.g. `bravery_data.starters.1`.
+pub fn export_game(path: &Path, format: Format, game: &Game) -> Result<()> {
+    let mut file = create(path)?;
+
+    match format {
+        Format::Csv => {
+            let mut fields = vec![];
+            let value = serde_json::to_value(game).map_err(io::Error::from)?;
+
+            flatten(String::new(), value, &mut fields);
+            write_csv(
+                &mut file,
+                &[String::from("Field"), String::from("Value")],
+                &fields,
+            )?;
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut file, game).map_err(io::Error::from)?;
+        }
+    }
+
+    Ok(file.flush()?)
+}
+
+/// Exports the calls to the random number generator traced while generating a game,
+/// as one JSON object per line to compare traces line by line.
+pub fn export_trace(path: &Path, calls: &[TracedCall]) -> Result<()> {
+    let mut file = create(path)?;
+
+    for call in calls {
+        serde_json::to_writer(&mut file, call).map_err(io::Error::from)?;
+        writeln!(file)?;
+    }
+
+    Ok(file.flush()?)
+}
+
+/// Creates a file to export to, along with its directory.
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    // Creating the file reports the error if the directory could not be created
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+
+    Ok(BufWriter::new(File::create(path)?))
+}
+
+/// Returns the columns exported for the selected modes.
+fn columns(is_randomizer: bool, is_bravery: bool, is_relic: bool) -> Result<Vec<Column>> {
+    let mut columns = vec![];
+
+    // The Randomizer table starts after the spectral familiars
+    if is_randomizer {
+        for index in 0..db::column_names(TABLES[0])?.len() {
+            columns.push(Column {
+                name: format!("Randomizer.{}", monster!(index + 4).name),
+                table: 0,
+                index,
+                is_relic: false,
+            });
+        }
+    }
+
+    if is_bravery {
+        for (index, name) in db::column_names(TABLES[1])?
+            .into_iter()
+            .take(BRAVERY_KEY_COLUMNS)
+            .enumerate()
+        {
+            columns.push(Column {
+                name: format!("Bravery.{name}"),
+                table: 1,
+                index,
+                is_relic: false,
+            });
+        }
+    }
+
+    if is_relic {
+        for (index, name) in db::column_names(TABLES[2])?
+            .into_iter()
+            .take(GAME_DATA.areas.len())
+            .enumerate()
+        {
+            columns.push(Column {
+                name: format!("Relic.{name}"),
+                table: 2,
+                index,
+                is_relic: true,
+            });
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Reads the rows of a seed's game for the selected modes, as joined by the seed finder's query.
+fn read_rows(
+    reader: &mut dyn RowReader,
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> Result<[Option<Vec<Option<i64>>>; 3]> {
+    let seed = seed as i64;
+    let mut read = |enabled: bool, table: usize, id: i64| {
+        if enabled {
+            reader.read(TABLES[table], id)
+        } else {
+            Ok(None)
+        }
+    };
+
+    Ok([
+        read(is_randomizer, 0, seed)?,
+        read(is_bravery, 1, seed * 2 + is_randomizer as i64)?,
+        read(
+            is_relic,
+            2,
+            seed * 3 + db::relic_game(is_randomizer, is_bravery) as i64,
+        )?,
+    ])
+}
+
+/// Generates the rows of a seed's game for the selected modes, as they would be stored.
+fn generate_rows(
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> [Option<Vec<Option<i64>>>; 3] {
+    let Ok(game) = search::generate_game(seed, is_randomizer, is_bravery, is_relic) else {
+        return [None, None, None];
+    };
+
+    [
+        game.mapping
+            .as_deref()
+            .filter(|_| is_randomizer)
+            .map(db::randomizer_values),
+        game.bravery_data.as_ref().map(db::bravery_values),
+        game.relics.as_ref().map(db::relic_values),
+    ]
+}
+
+/// Returns the exported values of a seed, in the order of the header.
+fn record(
+    seed: &ScoredSeed,
+    columns: &[Column],
+    rows: &[Option<Vec<Option<i64>>>; 3],
+) -> Vec<Value> {
+    let preferred = seed
+        .matched
+        .iter()
+        .map(|x| Value::from(x + 1))
+        .collect::<Vec<Value>>();
+
+    [
+        Value::from(seed.seed),
+        Value::from(seed.score),
+        Value::from(preferred),
+    ]
+    .into_iter()
+    .chain(columns.iter().map(|column| {
+        let id = rows[column.table]
+            .as_ref()
+            .and_then(|x| x.get(column.index).copied().flatten());
+
+        match id {
+            Some(id) if column.is_relic => Value::from(relic_name(id as u32)),
+            Some(id) => Value::from(monster!(id).name.as_str()),
+            None => Value::Null,
+        }
+    }))
+    .collect()
+}
+
+/// An exported seed, as a JSON object whose fields keep the order of the header.
+struct Record<'a>(&'a [String], &'a [Value]);
+
+impl Serialize for Record<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+        for (name, value) in self.0.iter().zip(self.1) {
+            map.serialize_entry(name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Adds the values of a JSON value to a list, along with the path of their field.
+fn flatten(path: String, value: Value, fields: &mut Vec<Vec<Value>>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            String::from(key)
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(child(&key), value, fields);
+            }
+        }
+        Value::Array(list) => {
+            for (index, value) in list.into_iter().enumerate() {
+                flatten(child(&index.to_string()), value, fields);
+            }
+        }
+        value => fields.push(vec![Value::from(path), value]),
+    }
+}
+
+fn write_csv(file: &mut impl Write, header: &[String], records: &[Vec<Value>]) -> io::Result<()> {
+    let line = header.iter().map(|x| csv_field(x)).collect::<Vec<String>>();
+    writeln!(file, "{}", line.join(","))?;
+
+    for record in records {
+        let line = record
+            .iter()
+            .map(|x| csv_field(&csv_value(x)))
+            .collect::<Vec<String>>();
+
+        writeln!(file, "{}", line.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the text of a value in a CSV file, lists being separated by commas.
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(list) => list
+            .iter()
+            .map(csv_value)
+            .collect::<Vec<String>>()
+            .join(", "),
+        value => value.to_string(),
+    }
+}
+
+/// Quotes a CSV field if it holds a separator, a quote or a line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        String::from(text)
+    }
+}
+
+fn relic_name(id: u32) -> &'static str {
+    GAME_DATA
+        .relics
+        .iter()
+        .find(|x| x.id == id)
+        .map_or("", |x| &x.name)
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::{
+    seed_finder::{
+        backend::{Backend, Page, Result},
+        enums::expression::Expression,
+    },
+    structs::game::Game,
+};
+
+/// A condition seeds do not have to match, but which adds its weight to their score if they do.
+#[derive(Clone)]
+pub struct SoftCondition {
+    pub expression: Expression,
+    pub weight: i32,
+    /// Whether an `any` condition, or conditions joined with `|`, add the weight once
+    /// for each one matched, e.g. for each Light-shifted egg, rather than once.
+    pub is_per_match: bool,
+}
+
+impl SoftCondition {
+    /// Returns the conditions each adding the weight to the score.
+    fn parts(&self) -> &[Expression] {
+        match &self.expression {
+            Expression::Any(_, list) | Expression::Or(list) if self.is_per_match => list,
+            expression => std::slice::from_ref(expression),
+        }
+    }
+}
+
+impl fmt::Display for SoftCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.is_per_match {
+            true => write!(f, "{} ({:+} each)", self.expression, self.weight),
+            false => write!(f, "{} ({:+})", self.expression, self.weight),
+        }
+    }
+}
+
+impl FromStr for SoftCondition {
+    type Err = String;
+
+    /// Parses an expression, optionally preceded by its weight, e.g. `3=Vaero:starter`.
+    ///
+    /// The weight is 1 if it is left out, and followed by `*` to be added for each condition
+    /// of an `any` condition or joined with `|` matched, e.g. `2*=any Dragon:army`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (weight, expression) = match s.split_once('=') {
+            Some((weight, expression)) => (weight.trim(), expression),
+            None => ("1", s),
+        };
+
+        let (weight, is_per_match) = match weight.strip_suffix('*') {
+            Some("") => ("1", true),
+            Some(weight) => (weight.trim(), true),
+            None => (weight, false),
+        };
+
+        Ok(SoftCondition {
+            expression: expression.parse()?,
+            weight: weight
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid weight \"{weight}\"."))?,
+            is_per_match,
+        })
+    }
+}
+
+/// A seed found along with the soft conditions it matches.
+#[derive(Clone, Serialize)]
+pub struct ScoredSeed {
+    pub seed: u32,
+    /// The total weight of the soft conditions matched.
+    pub score: i32,
+    /// Indexes of the soft conditions matched.
+    pub matched: Vec<usize>,
+}
+
+/// Finds all seeds matching the conditions in a database, scored with the soft conditions
+/// and ranked from the highest score, only keeping those of a page if any.
+///
+/// Each soft condition takes one more search, restricted to the seeds found,
+/// or one per condition of an `any` condition or joined with `|` if weighted per match.
+/// Without soft conditions, only the seeds of the page are searched for.
+pub fn find_seeds(
+    backend: &dyn Backend,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: &[SoftCondition],
+    page: Option<Page>,
+) -> Result<Vec<ScoredSeed>> {
+    let seeds = match page {
+        Some(page) if soft_conditions.is_empty() => backend.find_seeds_page(
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            conditions.clone(),
+            page,
+        )?,
+        _ => backend.find_seeds(is_randomizer, is_bravery, is_relic, conditions.clone())?,
+    };
+
+    let mut seeds = seeds
+        .into_iter()
+        .map(|seed| ScoredSeed {
+            seed,
+            score: 0,
+            matched: vec![],
+        })
+        .collect::<Vec<ScoredSeed>>();
+
+    if seeds.is_empty() || soft_conditions.is_empty() {
+        return Ok(seeds);
+    }
+
+    for (i, soft) in soft_conditions.iter().enumerate() {
+        for part in soft.parts() {
+            let mut list = conditions.lock().unwrap().clone();
+            list.push(part.clone());
+
+            let matching = backend
+                .find_seeds(
+                    is_randomizer,
+                    is_bravery,
+                    is_relic,
+                    Arc::new(Mutex::new(list)),
+                )?
+                .into_iter()
+                .collect::<HashSet<u32>>();
+
+            for seed in seeds.iter_mut().filter(|x| matching.contains(&x.seed)) {
+                seed.score += soft.weight;
+
+                if seed.matched.last() != Some(&i) {
+                    seed.matched.push(i);
+                }
+            }
+        }
+    }
+
+    rank(&mut seeds);
+
+    Ok(match page {
+        Some(page) => page.slice(&seeds).to_vec(),
+        None => seeds,
+    })
+}
+
+/// Scores a generated game with the soft conditions it matches.
+pub fn score_game(game: &Game, soft_conditions: &[SoftCondition]) -> ScoredSeed {
+    let mut score = 0;
+    let mut matched = vec![];
+
+    for (i, soft) in soft_conditions.iter().enumerate() {
+        let count = soft.parts().iter().filter(|x| x.matches(game)).count() as i32;
+
+        if count > 0 {
+            score += soft.weight * count;
+            matched.push(i);
+        }
+    }
+
+    ScoredSeed {
+        seed: game.seed,
+        score,
+        matched,
+    }
+}
+
+/// Sorts seeds from the highest score, keeping the order of seeds with the same score.
+pub fn rank(seeds: &mut [ScoredSeed]) {
+    seeds.sort_by_key(|x| std::cmp::Reverse(x.score));
+}
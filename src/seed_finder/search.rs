@@ -0,0 +1,73 @@
+use std::{
+    ops::RangeInclusive,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+use crate::{
+    seed_finder::{
+        backend::{Error, Result},
+        db,
+        enums::expression::Expression,
+        score::{self, ScoredSeed, SoftCondition},
+    },
+    structs::{error::GenerationError, game::Game, game_manager},
+};
+
+/// Finds the seeds of a range matching the conditions by generating them, without a database.
+///
+/// Seeds are generated and matched in batches on one worker thread per CPU core, and `found`
+/// is called with the matching seeds of each batch, in order, as soon as they are known.
+/// They are scored with the soft conditions, but not ranked.
+///
+/// `progress` is called with the number of batches done and the total number of batches.
+/// Setting `stop` cancels the search with [`Error::Stopped`].
+#[allow(clippy::too_many_arguments)]
+pub fn search(
+    seeds: RangeInclusive<u32>,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: &[SoftCondition],
+    progress: impl Fn(u32, u32),
+    mut found: impl FnMut(&[ScoredSeed]),
+    stop: Arc<AtomicBool>,
+) -> Result<()> {
+    let expression = Expression::And(conditions.lock().unwrap().clone());
+
+    db::generate_batches(
+        &seeds,
+        0,
+        |seed| {
+            // Games that fail to generate are not stored, and never match
+            generate_game(seed, is_randomizer, is_bravery, is_relic)
+                .ok()
+                .filter(|game| expression.matches(game))
+                .map(|game| score::score_game(&game, soft_conditions))
+        },
+        progress,
+        &stop,
+        || Error::Stopped,
+        |_, batch| {
+            let seeds = batch.iter().flatten().cloned().collect::<Vec<ScoredSeed>>();
+
+            if !seeds.is_empty() {
+                found(&seeds);
+            }
+
+            Ok(())
+        },
+    )
+}
+
+/// Generates the game of a seed that the database query reads for the selected game modes.
+///
+/// Without Bravery, relics are those of the Randomizer + Relics game, as in the `Relic` table.
+pub fn generate_game(
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> std::result::Result<Game, GenerationError> {
+    game_manager::generate_game(seed, is_randomizer || !is_bravery, is_bravery, is_relic)
+}
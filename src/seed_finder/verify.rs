@@ -0,0 +1,208 @@
+use std::{
+    fmt,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use crate::seed_finder::{
+    backend::{Backend, Error, Result, RowReader},
+    db::{self, Row, SeedGames},
+};
+
+/// Number of seeds verified at once by a worker.
+const CHUNK_SIZE: u64 = 1000;
+
+/// A game stored in the database that differs from the one generated for its seed.
+pub struct Mismatch {
+    pub seed: u32,
+    /// The game modes that diverged, e.g. `Randomizer + Bravery`.
+    pub modes: &'static str,
+    pub table: &'static str,
+    pub id: i64,
+    pub difference: Difference,
+}
+
+pub enum Difference {
+    /// The game was generated, but its row is missing.
+    Missing,
+    /// The game fails to generate, but a row was stored.
+    Unexpected,
+    /// The stored value of this column differs.
+    Column(String),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Seed {}, {} ({}.Id = {}): ",
+            self.seed, self.modes, self.table, self.id
+        )?;
+
+        match &self.difference {
+            Difference::Missing => write!(f, "the row is missing"),
+            Difference::Unexpected => write!(f, "the row should not exist"),
+            Difference::Column(column) => write!(f, "{column} differs"),
+        }
+    }
+}
+
+/// Generates the seeds of a range again and compares them to the rows stored in the database.
+///
+/// Only every `step`-th seed is checked, starting from the first one, to sample large ranges.
+/// Seeds are split between one worker thread per CPU core, each with its own reader.
+///
+/// `progress` is called with the number of seeds checked and the total number of seeds to check.
+/// Setting `stop` cancels the verification with an error.
+pub fn verify(
+    backend: &dyn Backend,
+    seeds: RangeInclusive<u32>,
+    step: u32,
+    progress: impl Fn(u64, u64),
+    stop: Arc<AtomicBool>,
+) -> Result<Vec<Mismatch>> {
+    let first = *seeds.start() as u64;
+    let total = sample_count(&seeds, step);
+    let step = step.max(1) as u64;
+    let chunk_count = total.div_ceil(CHUNK_SIZE);
+
+    let names = db::column_names("Randomizer")?;
+    let names = [
+        ("Randomizer", names),
+        ("Bravery", db::column_names("Bravery")?),
+        ("Relic", db::column_names("Relic")?),
+    ];
+
+    let next_chunk = AtomicU64::new(0);
+    let workers = thread::available_parallelism().map_or(1, |x| x.get());
+
+    let mut mismatches = thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<Result<(u64, Vec<Mismatch>)>>();
+
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next_chunk = &next_chunk;
+            let stop = &stop;
+            let names = &names;
+
+            scope.spawn(move || {
+                let mut reader = match backend.reader() {
+                    Ok(reader) => reader,
+                    Err(err) => {
+                        sender.send(Err(err)).ok();
+                        return;
+                    }
+                };
+
+                loop {
+                    let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+
+                    if chunk >= chunk_count || stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    let start = chunk * CHUNK_SIZE;
+                    let end = (start + CHUNK_SIZE).min(total);
+
+                    let res = (start..end)
+                        .map(|x| verify_seed(reader.as_mut(), names, (first + x * step) as u32))
+                        .collect::<Result<Vec<Vec<Mismatch>>>>()
+                        .map(|x| (end - start, x.into_iter().flatten().collect()));
+
+                    // Stop at the first error, or if the receiver stopped
+                    let failed = res.is_err();
+
+                    if sender.send(res).is_err() || failed {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut mismatches = vec![];
+        let mut done = 0;
+
+        progress(done, total);
+
+        for res in receiver {
+            let (count, list) = res?;
+
+            mismatches.extend(list);
+            done += count;
+
+            if stop.load(Ordering::Relaxed) {
+                return Err(Error::Stopped);
+            }
+
+            progress(done, total);
+        }
+
+        // Workers may have stopped before the receiver noticed
+        if done < total {
+            return Err(Error::Stopped);
+        }
+
+        Ok(mismatches)
+    })?;
+
+    mismatches.sort_by_key(|x| (x.seed, x.table, x.id));
+
+    Ok(mismatches)
+}
+
+/// Returns the number of seeds of a range checked when only every `step`-th one is.
+///
+/// Up to `u32::MAX + 1` for every seed, which does not fit in a `u32`.
+pub fn sample_count(seeds: &RangeInclusive<u32>, step: u32) -> u64 {
+    (*seeds.end() as u64).saturating_sub(*seeds.start() as u64) / step.max(1) as u64 + 1
+}
+
+/// Returns the rows of a seed that differ from its generated games.
+fn verify_seed(
+    reader: &mut dyn RowReader,
+    names: &[(&str, Vec<String>); 3],
+    seed: u32,
+) -> Result<Vec<Mismatch>> {
+    let games = SeedGames::new(seed);
+    let mut mismatches = vec![];
+
+    for row in games.rows() {
+        let stored = reader.read(row.table, row.id)?;
+        let (_, names) = names.iter().find(|(x, _)| *x == row.table).unwrap();
+
+        if let Some(difference) = compare_row(&row, stored, names) {
+            mismatches.push(Mismatch {
+                seed,
+                modes: row.modes,
+                table: row.table,
+                id: row.id,
+                difference,
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+fn compare_row(
+    row: &Row,
+    stored: Option<Vec<Option<i64>>>,
+    names: &[String],
+) -> Option<Difference> {
+    match (&row.values, stored) {
+        (Some(_), None) => Some(Difference::Missing),
+        (None, Some(_)) => Some(Difference::Unexpected),
+        (None, None) => None,
+        (Some(values), Some(stored)) => values
+            .iter()
+            .zip(&stored)
+            .position(|(x, y)| x != y)
+            .map(|i| Difference::Column(names[i].clone())),
+    }
+}
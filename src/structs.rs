@@ -1,7 +1,9 @@
+pub mod error;
 pub mod game;
 pub mod game_data;
 pub mod game_manager;
 pub mod map;
 pub mod modes;
 pub mod monster;
+pub mod random;
 pub mod relic;
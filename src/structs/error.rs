@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::data::GAME_DATA;
+
+/// Why a game could not be generated, along with how many random numbers were drawn until then.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GenerationError {
+    /// No Bravery eggs can meet the exploration requirements, which was known after this many tries.
+    ///
+    /// The game freezes on these seeds when starting a new save, e.g. seed 32410 with
+    /// the Randomizer and Bravery modes.
+    BraveryEggs { tries: u32, draws: u64 },
+    /// Every relic left was rejected for this area.
+    RelicPool { area: u32, draws: u64 },
+    /// This area or one of its scenes is missing from the game data.
+    MissingAreaData { area: u32, draws: u64 },
+}
+
+impl GenerationError {
+    /// Returns how many random numbers were drawn before generation failed.
+    pub fn draws(&self) -> u64 {
+        match self {
+            GenerationError::BraveryEggs { draws, .. }
+            | GenerationError::RelicPool { draws, .. }
+            | GenerationError::MissingAreaData { draws, .. } => *draws,
+        }
+    }
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let area_name = |area: &u32| {
+            GAME_DATA
+                .areas
+                .get(*area as usize)
+                .map_or(area.to_string(), |x| x.name.clone())
+        };
+
+        match self {
+            GenerationError::BraveryEggs { tries, .. } => {
+                let tries = if *tries == 1 {
+                    String::from("1 try")
+                } else {
+                    format!("{tries} tries")
+                };
+
+                write!(f, "no Bravery eggs could be found after {tries}")?
+            }
+            GenerationError::RelicPool { area, .. } => {
+                write!(f, "no relic is left for {}", area_name(area))?
+            }
+            GenerationError::MissingAreaData { area, .. } => {
+                write!(f, "the data of {} is missing", area_name(area))?
+            }
+        }
+
+        write!(f, " ({} random numbers drawn)", self.draws())
+    }
+}
+
+impl std::error::Error for GenerationError {}
@@ -25,12 +25,3 @@ impl PartialEq for Game {
             && self.relics == other.relics
     }
 }
-
-impl Game {
-    /// Returns true if this game fails to generate.
-    ///
-    /// This can happen for certain seeds with both Bravery and Randomizer modes enabled.
-    pub fn is_bad_seed(&self) -> bool {
-        self.is_bravery && self.bravery_data.is_none()
-    }
-}
@@ -20,6 +20,12 @@ pub struct GameData {
     pub relics: Vec<Relic>,
 }
 
+impl Default for GameData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GameData {
     pub fn new() -> GameData {
         GameData {
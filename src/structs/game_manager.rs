@@ -1,39 +1,82 @@
-use unity_random::Random;
-
 use crate::structs::{
+    error::GenerationError,
     game::Game,
     modes::{bravery::BraveryMode, randomizer::RandomizerMode, relic::RelicMode},
+    random::{CountingRandom, Rng, TracedCall},
 };
 
-pub fn generate_game(seed: u32, is_randomizer: bool, is_bravery: bool, is_relic: bool) -> Game {
-    let mut random = Random::new();
+/// Generates the game of a seed for the selected game modes, as the game does on a new save.
+pub fn generate_game(
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> Result<Game, GenerationError> {
+    let mut random = CountingRandom::new(seed);
+
+    generate(&mut random, seed, is_randomizer, is_bravery, is_relic)
+}
+
+/// Generates the game of a seed like `generate_game`, along with every call made
+/// to the random number generator, even if the game fails to generate.
+pub fn generate_game_traced(
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> (Result<Game, GenerationError>, Vec<TracedCall>) {
+    let mut random = CountingRandom::traced(seed);
+    let game = generate(&mut random, seed, is_randomizer, is_bravery, is_relic);
+
+    (game, random.into_trace())
+}
+
+/// Generates a game like `generate_game`, drawing numbers from another random number generator,
+/// e.g. one replaying the numbers drawn by the game. The seed is only recorded in the game.
+pub fn generate_game_with<R: Rng>(
+    random: R,
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> Result<Game, GenerationError> {
+    let mut random = CountingRandom::wrap(random);
 
-    random.init_state(seed as i32);
+    generate(&mut random, seed, is_randomizer, is_bravery, is_relic)
+}
 
+fn generate<R: Rng>(
+    random: &mut CountingRandom<R>,
+    seed: u32,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+) -> Result<Game, GenerationError> {
     let mapping = if is_randomizer {
-        Some(RandomizerMode::get_mapping(&mut random))
+        Some(RandomizerMode::get_mapping(random))
     } else {
         None
     };
 
     let bravery_data = if is_bravery {
-        BraveryMode::get_monsters(&mut random, is_randomizer, &mapping)
+        Some(BraveryMode::get_monsters(random, is_randomizer, &mapping)?)
     } else {
         None
     };
 
-    Game {
+    let relics = if is_relic {
+        Some(RelicMode::get_relics(random, bravery_data.as_ref())?)
+    } else {
+        None
+    };
+
+    Ok(Game {
         seed,
         is_randomizer,
         is_bravery,
         is_relic,
-        // Only generate if the seed didn't fail to generate bravery data
-        relics: if is_relic && (!is_bravery || (is_bravery && bravery_data.is_some())) {
-            RelicMode::get_relics(&mut random, is_bravery, &bravery_data)
-        } else {
-            None
-        },
-        bravery_data,
         mapping,
-    }
+        bravery_data,
+        relics,
+    })
 }
@@ -1,5 +1,6 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
-use unity_random::Random;
 
 use crate::{
     data::{
@@ -7,8 +8,10 @@ use crate::{
         GAME_DATA,
     },
     structs::{
+        error::GenerationError,
         map::Area,
         monster::{Ability, EMonster},
+        random::{CountingRandom, Rng},
     },
 };
 
@@ -39,13 +42,48 @@ macro_rules! has_egg_ability {
     };
 }
 
-#[derive(Clone, Copy)]
+/// Areas whose egg can provide each explore ability the game requires.
+const BREAKWALL_AREAS: [Area; 2] = [Area::BlueCaves, Area::MountainPath];
+const MOUNT_AREAS: [Area; 6] = [
+    Area::BlueCaves,
+    Area::MountainPath,
+    Area::StrongholdDungeon,
+    Area::AncientWoods,
+    Area::SnowyPeaks,
+    Area::SunPalace,
+];
+const MOUNT_OR_FLYING_AREAS: [Area; 4] = [
+    Area::BlueCaves,
+    Area::MountainPath,
+    Area::StrongholdDungeon,
+    Area::AncientWoods,
+];
+const IMPROVED_FLYING_AREAS: [Area; 6] = [
+    Area::StrongholdDungeon,
+    Area::AncientWoods,
+    Area::SnowyPeaks,
+    Area::SunPalace,
+    Area::HorizonBeach,
+    Area::MagmaChamber,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Shift {
     Normal = 0,
     Light = 1,
     Dark = 2,
 }
 
+impl fmt::Display for Shift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Shift::Normal => write!(f, "Normal"),
+            Shift::Light => write!(f, "Light"),
+            Shift::Dark => write!(f, "Dark"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BraveryData {
     pub shift_offset: u32,
@@ -78,7 +116,8 @@ impl PartialEq for BraveryData {
 }
 
 impl BraveryData {
-    const SHIFT_DATA: [Shift; 32] = [
+    /// Shift of each egg, starting from `shift_offset` and repeating itself.
+    pub const SHIFT_DATA: [Shift; 32] = [
         Shift::Dark,
         Shift::Normal,
         Shift::Dark,
@@ -113,6 +152,13 @@ impl BraveryData {
         Shift::Normal,
     ];
 
+    /// Returns the positions in `SHIFT_DATA` with this shift.
+    pub fn get_shift_positions(shift: Shift) -> Vec<u32> {
+        (0..BraveryData::SHIFT_DATA.len() as u32)
+            .filter(|x| BraveryData::SHIFT_DATA[*x as usize] == shift)
+            .collect()
+    }
+
     pub fn get_area_eggs_shift(&self) -> Vec<Shift> {
         self.eggs
             .iter()
@@ -138,8 +184,8 @@ impl BraveryData {
     }
 }
 
-pub struct BraveryMode<'a> {
-    random: &'a mut Random,
+pub struct BraveryMode<'a, R: Rng> {
+    random: &'a mut CountingRandom<R>,
 
     is_randomizer: bool,
     mapping: &'a Option<Vec<Option<u32>>>,
@@ -147,12 +193,13 @@ pub struct BraveryMode<'a> {
     monsters: BraveryData,
 }
 
-impl<'a> BraveryMode<'a> {
+impl<'a, R: Rng> BraveryMode<'a, R> {
+    /// Generates the Bravery monsters, or fails if no eggs meet the exploration requirements.
     pub fn get_monsters(
-        random: &mut Random,
+        random: &mut CountingRandom<R>,
         is_randomizer: bool,
         mapping: &Option<Vec<Option<u32>>>,
-    ) -> Option<BraveryData> {
+    ) -> Result<BraveryData, GenerationError> {
         let mut instance = BraveryMode {
             random,
 
@@ -175,11 +222,15 @@ impl<'a> BraveryMode<'a> {
             },
         };
 
+        instance
+            .random
+            .stage(|| String::from("Bravery swimming monster"));
         instance.monsters.swimming = GAME_DATA.swimming_monsters[instance
             .random
             .range_int(0, GAME_DATA.swimming_monsters.len() as i32)
             as usize];
 
+        instance.random.stage(|| String::from("Bex"));
         instance.monsters.bex = instance.determine_random_monster(true, false, false);
         instance.determine_starters();
 
@@ -188,30 +239,41 @@ impl<'a> BraveryMode<'a> {
         while !instance.determine_eggs() {
             tries += 1;
 
-            if tries % 100 == 0 {
-                instance.determine_starters();
-            }
-
             // Some seeds cannot generate, and will freeze the game upon creation
             // The algorithm is not able to generate randomizer and/or bravery monsters
-            // and will try forever
-            if tries > 10000 {
-                return None;
+            // and will try forever, as rerolling the starters cannot make up for the eggs
+            if (tries == 1 && !instance.can_meet_requirements()) || tries > 10000 {
+                return Err(GenerationError::BraveryEggs {
+                    tries,
+                    draws: instance.random.draws(),
+                });
+            }
+
+            if tries % 100 == 0 {
+                instance.determine_starters();
             }
         }
 
+        instance.random.stage(|| String::from("cryomancer"));
         instance.monsters.cryomancer = Some(instance.determine_random_monster(true, false, true));
+        instance
+            .random
+            .stage(|| String::from("cryomancer required"));
         instance.monsters.cryomancer_required = instance.determine_cryomancer_required();
         instance.determine_army();
 
-        for _ in 0..3 {
+        for i in 0..3 {
+            instance
+                .random
+                .stage(|| format!("End of Time monster {}", i + 1));
             let monster = instance.determine_random_monster(true, true, true);
             instance.monsters.end_of_time.push(monster);
         }
 
+        instance.random.stage(|| String::from("shift offset"));
         instance.monsters.shift_offset = instance.random.range_int(0, 1000) as u32;
 
-        Some(instance.monsters)
+        Ok(instance.monsters)
     }
 
     fn determine_random_monster(
@@ -229,11 +291,7 @@ impl<'a> BraveryMode<'a> {
             || (!allow_swimming && GAME_DATA.swimming_monsters.contains(&monster))
             || self.was_monster_already_determined(monster)
         {
-            return self.determine_random_monster(
-                allow_improved_flying,
-                allow_swimming,
-                allow_familiar,
-            );
+            self.determine_random_monster(allow_improved_flying, allow_swimming, allow_familiar)
         } else {
             monster
         }
@@ -261,7 +319,7 @@ impl<'a> BraveryMode<'a> {
     }
 
     fn has_ability(&self, ability: Ability, monster: u32, exclude: Option<u32>) -> bool {
-        !exclude.is_some_and(|f| f == monster)
+        exclude.is_none_or(|f| f != monster)
             && ability!(ability)
                 .explore_actions
                 .contains(&monster!(monster).explore_action)
@@ -270,10 +328,12 @@ impl<'a> BraveryMode<'a> {
     fn determine_starters(&mut self) {
         self.monsters.starters.clear();
 
+        self.random.stage(|| String::from("familiar"));
         self.monsters.familiar = self.random.range_int(0, 4) as u32;
         self.monsters.starters.push(self.monsters.familiar);
 
-        for _ in 0..2 {
+        for i in 0..2 {
+            self.random.stage(|| format!("starter {}", i + 1));
             let monster = self.determine_random_monster(false, false, false);
             self.monsters.starters.push(monster);
 
@@ -298,11 +358,14 @@ impl<'a> BraveryMode<'a> {
                 || monster == EMonster::Amberlagna as u32
             {
                 // self.random.skip(1);
+                self.random
+                    .stage(|| format!("instantiating starter {}", i + 1));
                 self.random.value();
             }
         }
     }
 
+    #[allow(clippy::excessive_precision, clippy::nonminimal_bool)]
     fn determine_eggs(&mut self) -> bool {
         self.monsters.eggs.clear();
 
@@ -310,6 +373,8 @@ impl<'a> BraveryMode<'a> {
             let mut egg = 0;
             let mut best_rating = -1.0;
 
+            self.random.stage(|| format!("egg for {}", area.name));
+
             for monster in &area.monsters {
                 let replacement = self.get_replacement_monster(monster);
 
@@ -343,6 +408,74 @@ impl<'a> BraveryMode<'a> {
             && self.has_secret_vision_monster(None)
     }
 
+    /// Returns false if no eggs can meet the exploration requirements, whichever starters are picked.
+    ///
+    /// This assumes the best case: any starter `determine_starters` could pick, and any monster
+    /// of an area or Tanuki as its egg, except the swimming monster and Bex which never change.
+    fn can_meet_requirements(&self) -> bool {
+        let is_fixed =
+            |monster: &u32| *monster == self.monsters.swimming || *monster == self.monsters.bex;
+
+        let eggs = GAME_DATA
+            .areas
+            .iter()
+            .map(|area| {
+                area.monsters
+                    .iter()
+                    .map(|x| self.get_replacement_monster(x))
+                    .chain([EMonster::Tanuki as u32])
+                    .filter(|x| !is_fixed(x))
+                    .collect::<Vec<u32>>()
+            })
+            .collect::<Vec<Vec<u32>>>();
+
+        let starters = (4..GAME_DATA.monsters.len() as u32 - 1)
+            .filter(|x| {
+                !self.has_ability(Ability::ImprovedFlying, *x, None)
+                    && !GAME_DATA.swimming_monsters.contains(x)
+                    && !is_fixed(x)
+            })
+            .collect::<Vec<u32>>();
+
+        let in_eggs = |ability: Ability, areas: &[usize]| {
+            areas.iter().any(|area| {
+                eggs[*area]
+                    .iter()
+                    .any(|x| self.has_ability(ability, *x, None))
+            })
+        };
+        let in_starters = |ability: Ability, with_familiar: bool| {
+            starters
+                .iter()
+                .chain(if with_familiar {
+                    &[0, 1, 2, 3][..]
+                } else {
+                    &[]
+                })
+                .any(|x| self.has_ability(ability, *x, None))
+        };
+
+        let secret_vision_areas = (0..GAME_DATA.areas.len())
+            .filter(|x| *x != Area::ForgottenWorld as usize)
+            .collect::<Vec<usize>>();
+
+        (in_starters(Ability::BreakWall, true)
+            || in_eggs(Ability::BreakWall, &BREAKWALL_AREAS.map(|x| x as usize)))
+            && (in_starters(Ability::Mount, false)
+                || in_eggs(Ability::Mount, &MOUNT_AREAS.map(|x| x as usize)))
+            && (in_starters(Ability::Mount, false)
+                || in_starters(Ability::Flying, true)
+                || in_eggs(Ability::Mount, &MOUNT_OR_FLYING_AREAS.map(|x| x as usize))
+                || in_eggs(Ability::Flying, &MOUNT_OR_FLYING_AREAS.map(|x| x as usize)))
+            && in_eggs(
+                Ability::ImprovedFlying,
+                &IMPROVED_FLYING_AREAS.map(|x| x as usize),
+            )
+            && (in_starters(Ability::SecretVision, false)
+                || in_eggs(Ability::SecretVision, &secret_vision_areas)
+                || self.has_ability(Ability::SecretVision, self.monsters.bex, None))
+    }
+
     fn determine_cryomancer_required(&mut self) -> u32 {
         let mut best_monster: u32 = 0;
         let mut best_rating: f32 = -1.;
@@ -383,7 +516,7 @@ impl<'a> BraveryMode<'a> {
 
         let rating = self.random.range_float(0., 1.);
 
-        if rating <= best_rating as f32 {
+        if rating <= best_rating {
             return None;
         }
 
@@ -404,6 +537,9 @@ impl<'a> BraveryMode<'a> {
     }
 
     fn determine_army_monster(&mut self, ability: Ability) {
+        self.random
+            .stage(|| format!("army monster for {}", ability!(ability).name));
+
         if self.has_endgame_ability(ability) {
             let monster = self.determine_random_monster(true, false, true);
             self.monsters.army.push(Some(monster));
@@ -435,7 +571,7 @@ impl<'a> BraveryMode<'a> {
 
     fn get_replacement_monster(&self, monster: &u32) -> u32 {
         if self.is_randomizer {
-            if let Some(id) = self.mapping.as_ref().unwrap()[*monster as usize] {
+            if let Some(Some(id)) = self.mapping.as_ref().map(|x| x[*monster as usize]) {
                 return id;
             }
         }
@@ -468,12 +604,7 @@ impl<'a> BraveryMode<'a> {
     fn has_breakwall_monster(&self, exclude: Option<u32>) -> bool {
         has_starter_ability!(self, Ability::BreakWall, exclude, true);
 
-        has_egg_ability!(
-            self,
-            Ability::BreakWall,
-            [Area::BlueCaves, Area::MountainPath],
-            exclude
-        );
+        has_egg_ability!(self, Ability::BreakWall, BREAKWALL_AREAS, exclude);
 
         false
     }
@@ -481,19 +612,7 @@ impl<'a> BraveryMode<'a> {
     fn has_mount_monster(&self, exclude: Option<u32>) -> bool {
         has_starter_ability!(self, Ability::Mount, exclude, false);
 
-        has_egg_ability!(
-            self,
-            Ability::Mount,
-            [
-                Area::BlueCaves,
-                Area::MountainPath,
-                Area::StrongholdDungeon,
-                Area::AncientWoods,
-                Area::SnowyPeaks,
-                Area::SunPalace
-            ],
-            exclude
-        );
+        has_egg_ability!(self, Ability::Mount, MOUNT_AREAS, exclude);
 
         false
     }
@@ -502,29 +621,8 @@ impl<'a> BraveryMode<'a> {
         has_starter_ability!(self, Ability::Mount, exclude, false);
         has_starter_ability!(self, Ability::Flying, exclude, true);
 
-        has_egg_ability!(
-            self,
-            Ability::Mount,
-            [
-                Area::BlueCaves,
-                Area::MountainPath,
-                Area::StrongholdDungeon,
-                Area::AncientWoods
-            ],
-            exclude
-        );
-
-        has_egg_ability!(
-            self,
-            Ability::Flying,
-            [
-                Area::BlueCaves,
-                Area::MountainPath,
-                Area::StrongholdDungeon,
-                Area::AncientWoods
-            ],
-            exclude
-        );
+        has_egg_ability!(self, Ability::Mount, MOUNT_OR_FLYING_AREAS, exclude);
+        has_egg_ability!(self, Ability::Flying, MOUNT_OR_FLYING_AREAS, exclude);
 
         false
     }
@@ -533,14 +631,7 @@ impl<'a> BraveryMode<'a> {
         has_egg_ability!(
             self,
             Ability::ImprovedFlying,
-            [
-                Area::StrongholdDungeon,
-                Area::AncientWoods,
-                Area::SnowyPeaks,
-                Area::SunPalace,
-                Area::HorizonBeach,
-                Area::MagmaChamber
-            ],
+            IMPROVED_FLYING_AREAS,
             exclude
         );
 
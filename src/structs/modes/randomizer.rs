@@ -1,5 +1,3 @@
-use unity_random::Random;
-
 use crate::{
     data::{
         macros::{ability, area, is_monster_in_area, monster},
@@ -8,18 +6,19 @@ use crate::{
     structs::{
         map::{Area, MapArea},
         monster::{Ability, EMonster},
+        random::{CountingRandom, Rng},
     },
 };
 
-pub struct RandomizerMode<'a> {
-    random: &'a mut Random,
+pub struct RandomizerMode<'a, R: Rng> {
+    random: &'a mut CountingRandom<R>,
 
     pool: Vec<u32>,
     mapping: Vec<Option<u32>>,
 }
 
-impl<'a> RandomizerMode<'a> {
-    pub fn get_mapping(random: &mut Random) -> Vec<Option<u32>> {
+impl<'a, R: Rng> RandomizerMode<'a, R> {
+    pub fn get_mapping(random: &mut CountingRandom<R>) -> Vec<Option<u32>> {
         let mut instance = RandomizerMode {
             random,
 
@@ -37,6 +36,9 @@ impl<'a> RandomizerMode<'a> {
         self.pool = (4..110).collect();
         self.mapping.fill(None);
 
+        self.random
+            .stage(|| String::from("Randomizer swimming monster"));
+
         let swimming_monster = GAME_DATA.swimming_monsters[self
             .random
             .range_int(0, GAME_DATA.swimming_monsters.len() as i32)
@@ -52,11 +54,14 @@ impl<'a> RandomizerMode<'a> {
         self.mapping[EMonster::Koi as usize] = Some(swimming_monster);
 
         // All other monsters
-        for monster in 4..110 as u32 {
+        for monster in 4..110_u32 {
             if monster == EMonster::Koi as u32 {
                 continue;
             }
 
+            self.random
+                .stage(|| format!("Randomizer replacement of {}", monster!(monster).name));
+
             let randomizer_monster = self.determine_randomizer_monster(
                 monster != EMonster::Tanuki as u32
                     && !is_monster_in_area!(Area::BlueCaves, &monster)
@@ -145,7 +150,7 @@ impl<'a> RandomizerMode<'a> {
     }
 
     fn get_replacement_monster(&self, monster: &u32) -> u32 {
-        self.mapping[*monster as usize].or(Some(*monster)).unwrap()
+        self.mapping[*monster as usize].unwrap_or(*monster)
     }
 
     fn has_randomizer_monsters_ability(&self, ability: Ability, areas: &[usize]) -> bool {
@@ -161,7 +166,7 @@ impl<'a> RandomizerMode<'a> {
     fn has_randomizer_secret_vision(&self) -> bool {
         for area in &GAME_DATA.areas {
             if area.id != Area::ForgottenWorld as u32
-                && self.has_monster_in_area_ability(&area, &Ability::SecretVision, true)
+                && self.has_monster_in_area_ability(area, &Ability::SecretVision, true)
             {
                 return true;
             }
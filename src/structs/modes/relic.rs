@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
-use unity_random::Random;
 
 use crate::{
     data::{
         macros::{area, monster},
         GAME_DATA,
     },
-    structs::modes::bravery::BraveryData,
+    structs::{
+        error::GenerationError,
+        modes::bravery::BraveryData,
+        random::{CountingRandom, Rng},
+        relic::Relic,
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,24 +25,22 @@ impl PartialEq for RelicData {
     }
 }
 
-pub struct RelicMode<'a> {
-    random: &'a mut Random,
+pub struct RelicMode<'a, R: Rng> {
+    random: &'a mut CountingRandom<R>,
 
-    is_bravery: bool,
-    bravery_data: &'a Option<BraveryData>,
+    bravery_data: Option<&'a BraveryData>,
 
     list: Vec<u32>,
 }
-impl<'a> RelicMode<'a> {
+impl<'a, R: Rng> RelicMode<'a, R> {
+    /// Generates the relic of each area, restricted to the monster types of the Bravery monsters if any.
     pub fn get_relics(
-        random: &mut Random,
-        is_bravery: bool,
-        bravery_data: &Option<BraveryData>,
-    ) -> Option<RelicData> {
+        random: &mut CountingRandom<R>,
+        bravery_data: Option<&BraveryData>,
+    ) -> Result<RelicData, GenerationError> {
         let mut instance = RelicMode {
             random,
 
-            is_bravery,
             bravery_data,
 
             list: vec![],
@@ -47,66 +49,110 @@ impl<'a> RelicMode<'a> {
         let mut area_chests = vec![];
 
         for area in 0..GAME_DATA.areas.len() as u32 {
-            let random_relic = instance.get_random_relic(area);
-            let random_chest = instance.get_random_chest_in_area(area);
+            let random_relic = instance.get_random_relic(area)?;
+            let random_chest = instance.get_random_chest_in_area(area)?;
 
             instance.list.push(random_relic);
             area_chests.push(random_chest);
         }
 
-        Some(RelicData {
+        Ok(RelicData {
             list: instance.list,
             area_chests,
         })
     }
 
-    fn get_random_relic(&mut self, area: u32) -> u32 {
-        let random_relic =
-            &GAME_DATA.relics[self.random.range_int(0, GAME_DATA.relics.len() as i32) as usize];
+    fn get_random_relic(&mut self, area: u32) -> Result<u32, GenerationError> {
+        let mut rejected = 0;
 
-        if self.list.contains(&random_relic.id) {
-            return self.get_random_relic(area);
-        }
+        self.random
+            .stage(|| format!("relic for {}", area!(area).name));
 
-        if self.is_bravery && random_relic.monster_type_restriction != 0 {
-            let bravery_data = self.bravery_data.as_ref().unwrap();
-            let mut monster_type_list: Vec<u32> = vec![];
+        loop {
+            let random_relic =
+                &GAME_DATA.relics[self.random.range_int(0, GAME_DATA.relics.len() as i32) as usize];
 
-            for (i, egg) in bravery_data.eggs.iter().enumerate() {
-                if i as u32 == area {
-                    for monster_type in &monster!(*egg as u32).monster_types {
-                        monster_type_list.push(*monster_type);
-                    }
-                }
+            if self.is_relic_allowed(random_relic, area) {
+                return Ok(random_relic.id);
+            }
+
+            rejected += 1;
+
+            // Only checked when unusually many relics were rejected, to draw as the game does
+            if rejected % GAME_DATA.relics.len() == 0
+                && !GAME_DATA
+                    .relics
+                    .iter()
+                    .any(|x| self.is_relic_allowed(x, area))
+            {
+                return Err(GenerationError::RelicPool {
+                    area,
+                    draws: self.random.draws(),
+                });
             }
+        }
+    }
+
+    /// Returns true if a relic was not placed yet, and fits the monster types of this area's
+    /// Bravery egg or starters if it is restricted to one.
+    fn is_relic_allowed(&self, relic: &Relic, area: u32) -> bool {
+        if self.list.contains(&relic.id) {
+            return false;
+        }
+
+        let Some(bravery_data) = self.bravery_data else {
+            return true;
+        };
+
+        if relic.monster_type_restriction == 0 {
+            return true;
+        }
 
-            for starter in &bravery_data.starters {
-                for monster_type in &monster!(*starter as u32).monster_types {
+        let mut monster_type_list: Vec<u32> = vec![];
+
+        for (i, egg) in bravery_data.eggs.iter().enumerate() {
+            if i as u32 == area {
+                for monster_type in &monster!(*egg).monster_types {
                     monster_type_list.push(*monster_type);
                 }
             }
+        }
 
-            if !monster_type_list.contains(&random_relic.monster_type_restriction) {
-                return self.get_random_relic(area);
+        for starter in &bravery_data.starters {
+            for monster_type in &monster!(*starter).monster_types {
+                monster_type_list.push(*monster_type);
             }
         }
 
-        random_relic.id
+        monster_type_list.contains(&relic.monster_type_restriction)
     }
 
-    fn get_random_chest_in_area(&mut self, area: u32) -> (String, u32) {
+    fn get_random_chest_in_area(&mut self, area: u32) -> Result<(String, u32), GenerationError> {
+        let missing = |random: &CountingRandom<R>| GenerationError::MissingAreaData {
+            area,
+            draws: random.draws(),
+        };
+
         let data = &area!(area).area_data;
 
+        self.random
+            .stage(|| format!("chest for {}", area!(area).name));
+
+        if data.is_empty() {
+            return Err(missing(self.random));
+        }
+
         let area_data_id = data[self.random.range_int(0, data.len() as i32) as usize];
         let area_data = GAME_DATA
             .area_data
             .iter()
             .find(|x| x.scene_id == area_data_id)
-            .unwrap();
+            .filter(|x| !x.chests.is_empty())
+            .ok_or_else(|| missing(self.random))?;
 
-        (
+        Ok((
             area_data.scene_name.to_owned(),
             area_data.chests[self.random.range_int(0, area_data.chests.len() as i32) as usize],
-        )
+        ))
     }
 }
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use unity_random::Random;
+
+/// A random number generator drawing numbers as Unity's `UnityEngine.Random` does,
+/// which the game modes are generated with.
+pub trait Rng {
+    /// Returns an integer from `min` included to `max` excluded, or `min` if they are equal.
+    fn range_int(&mut self, min: i32, max: i32) -> i32;
+    /// Returns a float from `min` to `max`, both included.
+    fn range_float(&mut self, min: f32, max: f32) -> f32;
+    /// Returns a float from 0 to 1, both included.
+    fn value(&mut self) -> f32;
+}
+
+impl Rng for Random {
+    fn range_int(&mut self, min: i32, max: i32) -> i32 {
+        Random::range_int(self, min, max)
+    }
+
+    fn range_float(&mut self, min: f32, max: f32) -> f32 {
+        Random::range_float(self, min, max)
+    }
+
+    fn value(&mut self) -> f32 {
+        Random::value(self)
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn range_int(&mut self, min: i32, max: i32) -> i32 {
+        (**self).range_int(min, max)
+    }
+
+    fn range_float(&mut self, min: f32, max: f32) -> f32 {
+        (**self).range_float(min, max)
+    }
+
+    fn value(&mut self) -> f32 {
+        (**self).value()
+    }
+}
+
+/// A call to the random number generator, as recorded in a trace.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TracedCall {
+    /// What the number was drawn for, e.g. `egg for Blue Caves`.
+    pub stage: String,
+    #[serde(flatten)]
+    pub call: Call,
+}
+
+/// A method of Unity's random number generator, with its arguments and result.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method")]
+pub enum Call {
+    RangeInt { min: i32, max: i32, result: i32 },
+    RangeFloat { min: f32, max: f32, result: f32 },
+    Value { result: f32 },
+}
+
+struct Trace {
+    stage: String,
+    calls: Vec<TracedCall>,
+}
+
+/// A random number generator, Unity's by default, counting the numbers it draws.
+///
+/// When tracing, every call is also recorded along with the current stage of the generation.
+pub struct CountingRandom<R: Rng = Random> {
+    random: R,
+    draws: u64,
+    trace: Option<Trace>,
+}
+
+impl CountingRandom {
+    /// Returns Unity's random number generator, initialized with a seed as the game does.
+    pub fn new(seed: u32) -> CountingRandom {
+        let mut random = Random::new();
+
+        random.init_state(seed as i32);
+
+        CountingRandom::wrap(random)
+    }
+
+    /// Returns Unity's random number generator recording every call, to read with `into_trace`.
+    pub fn traced(seed: u32) -> CountingRandom {
+        CountingRandom {
+            trace: Some(Trace {
+                stage: String::new(),
+                calls: vec![],
+            }),
+            ..CountingRandom::new(seed)
+        }
+    }
+}
+
+impl<R: Rng> CountingRandom<R> {
+    /// Counts the numbers drawn by another random number generator.
+    pub fn wrap(random: R) -> CountingRandom<R> {
+        CountingRandom {
+            random,
+            draws: 0,
+            trace: None,
+        }
+    }
+
+    /// Returns how many numbers were drawn so far.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Sets the stage of the next calls when tracing, only building its name then.
+    pub fn stage(&mut self, stage: impl FnOnce() -> String) {
+        if let Some(trace) = &mut self.trace {
+            trace.stage = stage();
+        }
+    }
+
+    /// Returns the calls recorded so far, or nothing if not tracing.
+    pub fn into_trace(self) -> Vec<TracedCall> {
+        self.trace.map_or(vec![], |x| x.calls)
+    }
+
+    pub fn range_int(&mut self, min: i32, max: i32) -> i32 {
+        // An empty range returns `min` without drawing
+        if min != max {
+            self.draws += 1;
+        }
+
+        let result = self.random.range_int(min, max);
+        self.record(Call::RangeInt { min, max, result });
+        result
+    }
+
+    pub fn range_float(&mut self, min: f32, max: f32) -> f32 {
+        self.draws += 1;
+
+        let result = self.random.range_float(min, max);
+        self.record(Call::RangeFloat { min, max, result });
+        result
+    }
+
+    pub fn value(&mut self) -> f32 {
+        self.draws += 1;
+
+        let result = self.random.value();
+        self.record(Call::Value { result });
+        result
+    }
+
+    fn record(&mut self, call: Call) {
+        if let Some(trace) = &mut self.trace {
+            trace.calls.push(TracedCall {
+                stage: trace.stage.clone(),
+                call,
+            });
+        }
+    }
+}
@@ -1,7 +1,6 @@
 pub mod condition;
 pub mod dialog;
 pub mod display;
-pub mod enums;
 pub mod finder;
 pub mod request;
 pub mod seed_info;
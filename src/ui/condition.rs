@@ -6,26 +6,33 @@ use slint::{
 
 use crate::{
     data::GAME_DATA,
-    structs::monster::EMonster,
-    ui::{
-        dialog,
-        enums::{condition::Condition, effect::Effect, value::Value},
+    seed_finder::{
+        enums::{effect::Effect, expression::Expression, value::Value},
+        score::SoftCondition,
     },
+    structs::{modes::bravery::Shift, monster::EMonster},
+    ui::dialog,
 };
 
-/// Adds a condition to the seed finder.
+/// Adds a condition to the seed finder, before the soft conditions.
+///
+/// If `is_or` is set, the condition is combined with the last one instead by `OR`.
+#[allow(clippy::too_many_arguments)]
 pub fn add_condition(
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
     rows: ModelRc<ModelRc<StandardListViewItem>>,
     value: Value,
     effect: Effect,
+    is_negated: bool,
+    is_or: bool,
     is_randomizer: bool,
     is_bravery: bool,
+    is_relic: bool,
     position: PhysicalPosition,
     size: PhysicalSize,
 ) {
-    let condition = Condition::from((value, effect));
-    let error = is_valid(&condition, is_randomizer, is_bravery);
+    let mut expression = Expression::from((value, effect));
+    let error = expression.validate(is_randomizer, is_bravery, is_relic);
 
     if let Some(err) = error {
         dialog::show_message(format!("Invalid condition: {}", err), position, size);
@@ -36,28 +43,106 @@ pub fn add_condition(
         .as_any()
         .downcast_ref::<VecModel<ModelRc<StandardListViewItem>>>()
     {
-        let condition_str = condition.to_string();
+        let mut conditions = conditions.lock().unwrap();
+
+        if is_negated {
+            expression = expression.negate();
+        }
+
+        if is_or {
+            match conditions.last() {
+                Some(last) => expression = last.clone().or(expression),
+                None => {
+                    dialog::show_message(
+                        String::from("Please add a condition to combine with first."),
+                        position,
+                        size,
+                    );
+                    return;
+                }
+            }
+        }
+
+        let expression_str = expression.to_string();
         let exists = model
             .iter()
-            .any(|x| x.iter().nth(0).unwrap().text == condition_str);
+            .any(|x| x.iter().next().unwrap().text == expression_str);
 
         if !exists {
-            let row = vec![
-                StandardListViewItem::from(SharedString::from(condition_str)),
+            let row = ModelRc::new(VecModel::from(vec![
+                StandardListViewItem::from(SharedString::from(expression_str)),
                 StandardListViewItem::from(SharedString::from("x")),
-            ];
+            ]));
+
+            if is_or {
+                model.set_row_data(conditions.len() - 1, row);
+                *conditions.last_mut().unwrap() = expression;
+            } else {
+                model.insert(conditions.len(), row);
+                conditions.push(expression);
+            }
+        }
+    }
+}
+
+/// Adds a soft condition to the seed finder, which seeds are ranked by.
+#[allow(clippy::too_many_arguments)]
+pub fn add_soft_condition(
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
+    rows: ModelRc<ModelRc<StandardListViewItem>>,
+    value: Value,
+    effect: Effect,
+    is_negated: bool,
+    weight: i32,
+    is_per_match: bool,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    position: PhysicalPosition,
+    size: PhysicalSize,
+) {
+    let mut expression = Expression::from((value, effect));
+    let error = expression.validate(is_randomizer, is_bravery, is_relic);
+
+    if let Some(err) = error {
+        dialog::show_message(format!("Invalid condition: {}", err), position, size);
+        return;
+    }
+
+    if weight == 0 {
+        dialog::show_message(
+            String::from("Please give the condition a weight other than 0."),
+            position,
+            size,
+        );
+        return;
+    }
 
-            model.push(ModelRc::new(VecModel::from(row)));
+    if let Some(model) = rows
+        .as_any()
+        .downcast_ref::<VecModel<ModelRc<StandardListViewItem>>>()
+    {
+        let mut soft_conditions = soft_conditions.lock().unwrap();
 
-            let mut conditions = conditions.lock().unwrap();
-            conditions.push(condition);
+        if is_negated {
+            expression = expression.negate();
         }
+
+        let soft = SoftCondition {
+            expression,
+            weight,
+            is_per_match,
+        };
+
+        model.push(soft_condition_row(soft_conditions.len(), &soft));
+        soft_conditions.push(soft);
     }
 }
 
-/// Removes a condition from the seed finder.
+/// Removes a condition or soft condition from the seed finder.
 pub fn remove_condition(
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
     rows: ModelRc<ModelRc<StandardListViewItem>>,
     row: i32,
 ) {
@@ -68,10 +153,30 @@ pub fn remove_condition(
         model.remove(row as usize);
 
         let mut conditions = conditions.lock().unwrap();
-        conditions.remove(row as usize);
+        let mut soft_conditions = soft_conditions.lock().unwrap();
+
+        if (row as usize) < conditions.len() {
+            conditions.remove(row as usize);
+            return;
+        }
+
+        soft_conditions.remove(row as usize - conditions.len());
+
+        // Soft conditions are numbered in the seeds they match
+        for (i, soft) in soft_conditions.iter().enumerate() {
+            model.set_row_data(conditions.len() + i, soft_condition_row(i, soft));
+        }
     }
 }
 
+/// Returns the row of the conditions' table showing the soft condition at this index.
+fn soft_condition_row(index: usize, soft: &SoftCondition) -> ModelRc<StandardListViewItem> {
+    ModelRc::new(VecModel::from(vec![
+        StandardListViewItem::from(SharedString::from(format!("Prefer {}: {soft}", index + 1))),
+        StandardListViewItem::from(SharedString::from("x")),
+    ]))
+}
+
 /// Initializes the values of the conditions' combobox.
 pub fn init_values(
     is_randomizer: bool,
@@ -89,6 +194,14 @@ pub fn init_values(
                 .filter(|x| x.id != EMonster::Bard as u32)
                 .map(|x| StandardListViewItem::from(SharedString::from(&x.name))),
         );
+
+        // Groups of monsters, skipping the `None` monster type
+        values.extend(
+            std::iter::once(Value::AnyMonster)
+                .chain((1..GAME_DATA.monster_types.len() as u32).map(Value::MonsterType))
+                .chain((0..GAME_DATA.abilities.len() as u32).map(Value::Ability))
+                .map(|x| StandardListViewItem::from(SharedString::from(x.to_string()))),
+        );
     }
 
     if is_relic {
@@ -100,7 +213,7 @@ pub fn init_values(
         );
     }
 
-    if values.len() == 0 {
+    if values.is_empty() {
         values.push(StandardListViewItem::from(SharedString::from("")));
     }
 
@@ -156,6 +269,20 @@ pub fn init_effects(
             )));
         }
 
+        for i in 0..GAME_DATA.areas.len() as u32 {
+            for shift in [Shift::Light, Shift::Dark] {
+                effects.push(StandardListViewItem::from(SharedString::from(
+                    Effect::ShiftedEggInArea(i, shift).to_string(),
+                )));
+            }
+        }
+
+        for shift in [Shift::Light, Shift::Dark] {
+            effects.push(StandardListViewItem::from(SharedString::from(
+                Effect::ShiftedArmy(shift).to_string(),
+            )));
+        }
+
         if is_randomizer || is_relic {
             for i in 0..GAME_DATA.areas.len() as u32 {
                 effects.push(StandardListViewItem::from(SharedString::from(
@@ -186,71 +313,17 @@ pub fn init_effects(
         }
     }
 
-    if effects.len() == 0 {
-        effects.push(StandardListViewItem::from(SharedString::from("")));
-    }
-
-    ModelRc::new(VecModel::from(effects))
-}
-
-fn is_valid(condition: &Condition, is_randomizer: bool, is_bravery: bool) -> Option<&str> {
-    if let Condition::Invalid(err) = condition {
-        return Some(err.as_str());
-    }
-
-    if let Condition::MonsterAvailable(_) = condition {
-        if is_randomizer && !is_bravery {
-            return Some("Every monster is available in Randomizer mode.");
-        }
-    }
-
-    if let Condition::BraveryChest(id) = condition {
-        if *id <= 3 {
-            return Some("Spectral familiars cannot be obtained in Bravery area chests.");
-        }
-    }
-
-    if let Condition::Swimming(id) = condition {
-        if !GAME_DATA.swimming_monsters.contains(id) {
-            return Some("This monster cannot be given at the Sun Palace.");
-        }
-    }
-
-    if let Condition::Bex(id) = condition {
-        if *id <= 3 {
-            return Some("Spectral familiars cannot be obtained from Bex.");
-        }
-    }
-
-    if let Condition::CryomancerRequired(id) = condition {
-        if *id <= 3 {
-            return Some("Spectral familiars cannot be wanted by the Cryomancer.");
-        }
-    }
-
-    if let Condition::EggInArea(id, _) = condition {
-        if *id <= 3 {
-            return Some("Spectral familiars cannot be obtained in Bravery area chests.");
-        }
-    }
-
-    if let Condition::MonsterInArea(id, _) = condition {
-        if *id <= 3 {
-            return Some("Spectral familiars cannot be found in any area.");
+    if is_relic {
+        for scene in &GAME_DATA.area_data {
+            effects.push(StandardListViewItem::from(SharedString::from(
+                Effect::InScene(scene.scene_id, None).to_string(),
+            )));
         }
     }
 
-    if let Condition::Replacement(monster, replacement) = condition {
-        if *monster <= 3 {
-            return Some(
-                "Spectral familiars cannot be replaced by any monster. You may be looking for the \"Eternity's End\" condition.",
-            );
-        }
-
-        if *monster == *replacement {
-            return Some("Cannot replace a monster with itself.");
-        }
+    if effects.is_empty() {
+        effects.push(StandardListViewItem::from(SharedString::from("")));
     }
 
-    None
+    ModelRc::new(VecModel::from(effects))
 }
@@ -76,7 +76,7 @@ pub struct Display<'a> {
 }
 
 impl<'a> Display<'a> {
-    pub fn new(data: &GameData) -> Display {
+    pub fn new(data: &GameData) -> Display<'_> {
         Display {
             monsters: image::load_from_memory(std::include_bytes!(
                 "../../res/out/atlas/monsters.png"
@@ -108,9 +108,8 @@ impl<'a> Display<'a> {
 
         let sprite = self.get_monster_sprite(monster);
         let replaces = replaces
-            .and_then(|id| Some(self.get_monster_sprite(&monster!(id).name)))
-            .or(Some(SharedPixelBuffer::new(0, 0)))
-            .unwrap();
+            .map(|id| self.get_monster_sprite(&monster!(id).name))
+            .unwrap_or(SharedPixelBuffer::new(0, 0));
 
         MonsterDisplayInfoSend {
             name: SharedString::from(name),
@@ -135,7 +134,7 @@ impl<'a> Display<'a> {
     }
 
     /// Returns an icon's sprite from the atlas.
-    pub fn get_icon(&self, name: &String) -> SharedPixelBuffer<Rgba8Pixel> {
+    pub fn get_icon(&self, name: &str) -> SharedPixelBuffer<Rgba8Pixel> {
         let mut name = name.replace(" ", "").replace("'", "");
 
         if name == "SunRing" || name == "MoonRing" {
@@ -149,7 +148,7 @@ impl<'a> Display<'a> {
     }
 
     /// Returns a monster's sprite from the atlas.
-    fn get_monster_sprite(&self, monster: &String) -> SharedPixelBuffer<Rgba8Pixel> {
+    fn get_monster_sprite(&self, monster: &str) -> SharedPixelBuffer<Rgba8Pixel> {
         let name = monster.replace(" ", "").replace("'", "");
 
         let mut image = self.get_image_from_atlas(&name, &self.monsters, &self.monsters_map);
@@ -161,7 +160,7 @@ impl<'a> Display<'a> {
     /// Returns all monster displays for an area.
     pub fn get_by_area(
         &self,
-        mapping: &Vec<Option<u32>>,
+        mapping: &[Option<u32>],
         bravery: &Option<BraveryData>,
         area_id: u32,
     ) -> Vec<MonsterDisplayInfoSend> {
@@ -194,19 +193,19 @@ impl<'a> Display<'a> {
                     self.get_monster(
                         mapping[*x as usize].unwrap(),
                         Some(*x),
-                        *(&bravery
+                        bravery
                             .eggs
                             .get(area_id as usize)
-                            .is_some_and(|y| *y == mapping[*x as usize].unwrap())),
+                            .is_some_and(|y| *y == mapping[*x as usize].unwrap()),
                         area.champions.contains(x),
                         Shift::Normal,
                     )
                 })
                 // Make sure we get all 14 displays, get empty ones as necessary
-                .chain(
-                    std::iter::repeat(self.get_monster_empty())
-                        .take(14 - &area.wild_monsters.len()),
-                )
+                .chain(std::iter::repeat_n(
+                    self.get_monster_empty(),
+                    14 - &area.wild_monsters.len(),
+                ))
                 .collect::<Vec<MonsterDisplayInfoSend>>();
 
             displays.append(&mut area_displays);
@@ -225,10 +224,10 @@ impl<'a> Display<'a> {
                     )
                 })
                 // Make sure we get all 14 displays, get empty ones as necessary
-                .chain(
-                    std::iter::repeat(self.get_monster_empty())
-                        .take(14 - &area.wild_monsters.len()),
-                )
+                .chain(std::iter::repeat_n(
+                    self.get_monster_empty(),
+                    14 - &area.wild_monsters.len(),
+                ))
                 .collect::<Vec<MonsterDisplayInfoSend>>();
 
             displays.append(&mut area_displays);
@@ -1,5 +1,6 @@
 use std::{
-    fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Mutex,
@@ -8,29 +9,105 @@ use std::{
 };
 
 use slint::{
-    CloseRequestResponse, ComponentHandle, ModelRc, PhysicalPosition, PhysicalSize, SharedString,
-    StandardListViewItem, VecModel, Weak,
+    CloseRequestResponse, ComponentHandle, Model, ModelRc, PhysicalPosition, PhysicalSize,
+    SharedString, StandardListViewItem, VecModel, Weak,
 };
 
 use crate::{
-    seed_finder::db,
+    seed_finder::{
+        backend::{self, Backend, Error, Page},
+        db,
+        enums::expression::Expression,
+        export::{self, Format},
+        score::{self, ScoredSeed, SoftCondition},
+        search,
+    },
     ui::{
         dialog,
-        enums::condition::Condition,
         types::{AppWindow, ProgressDialog},
     },
 };
 
+/// How many seeds are loaded into the seeds table at once.
+pub const PAGE_SIZE: u32 = 1000;
+
+/// The seeds found by the last search, from which more are loaded into the seeds table.
+pub struct FoundSeeds {
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    /// The database the seeds were found in, if any.
+    backend: Option<Box<dyn Backend>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    /// Every seed found, unless they are read from the database page by page.
+    seeds: Option<Vec<ScoredSeed>>,
+}
+
+impl FoundSeeds {
+    fn count(&self) -> backend::Result<u64> {
+        match (&self.seeds, &self.backend) {
+            (Some(seeds), _) => Ok(seeds.len() as u64),
+            (None, Some(backend)) => backend.count_seeds(
+                self.is_randomizer,
+                self.is_bravery,
+                self.is_relic,
+                self.conditions.clone(),
+            ),
+            (None, None) => Ok(0),
+        }
+    }
+
+    /// Returns the seeds of a page.
+    fn page(&self, page: Page) -> backend::Result<Vec<ScoredSeed>> {
+        match (&self.seeds, &self.backend) {
+            (Some(seeds), _) => Ok(page.slice(seeds).to_vec()),
+            (None, Some(backend)) => score::find_seeds(
+                backend.as_ref(),
+                self.is_randomizer,
+                self.is_bravery,
+                self.is_relic,
+                self.conditions.clone(),
+                &[],
+                Some(page),
+            ),
+            (None, None) => Ok(vec![]),
+        }
+    }
+
+    /// Writes every seed found to a file, along with the key columns of their games.
+    fn export(&self, path: &Path, format: Format) -> backend::Result<()> {
+        let seeds = self.page(Page {
+            offset: 0,
+            limit: u32::MAX,
+        })?;
+
+        export::export_seeds(
+            path,
+            format,
+            &seeds,
+            self.is_randomizer,
+            self.is_bravery,
+            self.is_relic,
+            self.backend.as_deref(),
+        )
+    }
+}
+
+/// Finds seeds in the database, showing how many were found and loading the first ones.
+#[allow(clippy::too_many_arguments)]
 pub fn find(
     ui_weak: Weak<AppWindow>,
+    database: PathBuf,
     is_randomizer: bool,
     is_bravery: bool,
     is_relic: bool,
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
     parent_position: PhysicalPosition,
     parent_size: PhysicalSize,
 ) {
-    if fs::metadata("./seeds.db").is_err() {
+    if !database.is_file() {
         dialog::show_question(
             ui_weak,
             String::from(
@@ -44,60 +121,398 @@ pub fn find(
         return;
     }
 
-    if !is_randomizer && !is_bravery {
-        dialog::show_message(
-            String::from("Please select at least the Randomizer or Bravery game modes."),
+    let backend = match backend::open(&database) {
+        Ok(backend) => backend,
+        Err(err) => {
+            dialog::show_message(err.to_string(), parent_position, parent_size);
+            return;
+        }
+    };
+
+    if let Ok(Some(reason)) = backend.outdated_reason() {
+        dialog::show_question(
+            ui_weak,
+            format!(
+                "The seeds database is outdated: {reason}.\nGenerate it again now?\nNote: this will take a while."
+            ),
             parent_position,
             parent_size,
+            generate_db,
         );
 
         return;
     }
 
-    if conditions.lock().unwrap().len() == 0 {
-        dialog::show_message(
-            String::from("Please add at least 1 condition."),
+    if !backend.is_complete().unwrap_or(false) {
+        dialog::show_question(
+            ui_weak,
+            String::from(
+                "The seeds database is incomplete. Resume its creation now?\nNote: this may take a while.",
+            ),
             parent_position,
             parent_size,
+            generate_db,
         );
 
         return;
     }
 
+    if !can_find(
+        is_randomizer,
+        is_bravery,
+        &conditions,
+        parent_position,
+        parent_size,
+    ) {
+        return;
+    }
+
+    ui_weak
+        .upgrade_in_event_loop(|ui| {
+            ui.set_found_count(0);
+            ui.set_loading_seeds(true);
+        })
+        .unwrap();
+
+    thread::spawn(move || {
+        let soft_conditions = soft_conditions.lock().unwrap().clone();
+        let first_page = Page {
+            offset: 0,
+            limit: PAGE_SIZE,
+        };
+
+        // Ranking needs all seeds at once
+        let seeds = if soft_conditions.is_empty() {
+            Ok(None)
+        } else {
+            score::find_seeds(
+                backend.as_ref(),
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                conditions.clone(),
+                &soft_conditions,
+                None,
+            )
+            .map(Some)
+        };
+
+        let res = seeds.and_then(|seeds| {
+            let found = FoundSeeds {
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                backend: Some(backend),
+                conditions,
+                seeds,
+            };
+
+            let count = found.count()?;
+            let seeds = found.page(first_page)?;
+            *found_seeds.lock().unwrap() = Some(found);
+
+            Ok((count, seeds))
+        });
+
+        ui_weak
+            .upgrade_in_event_loop(move |ui| {
+                ui.set_loading_seeds(false);
+
+                match res {
+                    Ok((count, seeds)) => {
+                        let model = seeds
+                            .iter()
+                            .map(seed_row)
+                            .collect::<Vec<ModelRc<StandardListViewItem>>>();
+
+                        ui.set_found_count(i32::try_from(count).unwrap_or(i32::MAX));
+                        ui.set_found_seeds(ModelRc::new(VecModel::from(model)));
+                    }
+                    Err(err) => dialog::show_message(
+                        format!("Could not find seeds: {err}"),
+                        parent_position,
+                        parent_size,
+                    ),
+                }
+            })
+            .unwrap();
+    });
+}
+
+/// Loads the next page of the seeds found into the seeds table, starting after the seeds already shown.
+pub fn load_seeds(
+    ui_weak: Weak<AppWindow>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+    offset: u32,
+    parent_position: PhysicalPosition,
+    parent_size: PhysicalSize,
+) {
     ui_weak
         .upgrade_in_event_loop(|ui| ui.set_loading_seeds(true))
         .unwrap();
 
     thread::spawn(move || {
-        let seeds = db::find_seeds(is_randomizer, is_bravery, is_relic, conditions);
+        let page = Page {
+            offset,
+            limit: PAGE_SIZE,
+        };
+
+        let res = match found_seeds.lock().unwrap().as_ref() {
+            Some(found) => found.page(page),
+            None => Ok(vec![]),
+        };
+
+        ui_weak
+            .upgrade_in_event_loop(move |ui| {
+                ui.set_loading_seeds(false);
+
+                match res {
+                    Ok(seeds) => {
+                        let rows = ui.get_found_seeds();
+
+                        if let Some(model) = rows
+                            .as_any()
+                            .downcast_ref::<VecModel<ModelRc<StandardListViewItem>>>()
+                        {
+                            for seed in &seeds {
+                                model.push(seed_row(seed));
+                            }
+                        }
+                    }
+                    Err(err) => dialog::show_message(
+                        format!("Could not load seeds: {err}"),
+                        parent_position,
+                        parent_size,
+                    ),
+                }
+            })
+            .unwrap();
+    });
+}
 
-        if let Ok(seeds) = seeds {
-            ui_weak
-                .upgrade_in_event_loop(move |ui| {
+/// Writes every seed found by the last search to a file in the data directory,
+/// along with the key columns of their games.
+pub fn export_seeds(
+    ui_weak: Weak<AppWindow>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+    format: Format,
+    parent_position: PhysicalPosition,
+    parent_size: PhysicalSize,
+) {
+    let path = export::default_path("seeds", format);
+
+    thread::spawn(move || {
+        let message = match found_seeds.lock().unwrap().as_ref() {
+            Some(found) => match found.export(&path, format) {
+                Ok(()) => format!("Seeds exported to {}", path.display()),
+                Err(err) => format!("Could not export the seeds: {err}"),
+            },
+            None => String::from("Please find seeds first."),
+        };
+
+        ui_weak
+            .upgrade_in_event_loop(move |_| {
+                dialog::show_message(message, parent_position, parent_size)
+            })
+            .unwrap();
+    });
+}
+
+/// Finds seeds by generating them instead of using the database,
+/// adding the first page of them to the seeds table as they are found.
+///
+/// With soft conditions, the table is ranked once the search ends.
+#[allow(clippy::too_many_arguments)]
+pub fn search(
+    ui_weak: Weak<AppWindow>,
+    seeds: RangeInclusive<u32>,
+    is_randomizer: bool,
+    is_bravery: bool,
+    is_relic: bool,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+    stop_signal: Arc<AtomicBool>,
+    parent_position: PhysicalPosition,
+    parent_size: PhysicalSize,
+) {
+    if !can_find(
+        is_randomizer,
+        is_bravery,
+        &conditions,
+        parent_position,
+        parent_size,
+    ) {
+        return;
+    }
+
+    stop_signal.store(false, Ordering::Relaxed);
+    *found_seeds.lock().unwrap() = None;
+
+    ui_weak
+        .upgrade_in_event_loop(|ui| {
+            ui.set_found_seeds(ModelRc::new(
+                VecModel::<ModelRc<StandardListViewItem>>::from(vec![]),
+            ));
+            ui.set_found_count(0);
+            ui.set_loading_seeds(true);
+        })
+        .unwrap();
+
+    thread::spawn(move || {
+        let found_weak = ui_weak.clone();
+        let found_conditions = conditions.clone();
+        let soft_conditions = soft_conditions.lock().unwrap().clone();
+        let mut all_seeds = vec![];
+
+        let res = search::search(
+            seeds,
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            conditions,
+            &soft_conditions,
+            |_, _| {},
+            |seeds| {
+                // Only the first page is shown while searching
+                let shown = PAGE_SIZE as usize - all_seeds.len().min(PAGE_SIZE as usize);
+                let seeds_shown = seeds[..shown.min(seeds.len())].to_vec();
+
+                all_seeds.extend_from_slice(seeds);
+
+                let count = i32::try_from(all_seeds.len()).unwrap_or(i32::MAX);
+
+                found_weak
+                    .upgrade_in_event_loop(move |ui| {
+                        let rows = ui.get_found_seeds();
+
+                        if let Some(model) = rows
+                            .as_any()
+                            .downcast_ref::<VecModel<ModelRc<StandardListViewItem>>>()
+                        {
+                            for seed in &seeds_shown {
+                                model.push(seed_row(seed));
+                            }
+                        }
+
+                        ui.set_found_count(count);
+                    })
+                    .unwrap();
+            },
+            stop_signal,
+        );
+
+        // Seeds are only ranked once all are found
+        let ranked = (!soft_conditions.is_empty()).then(|| {
+            score::rank(&mut all_seeds);
+            Page {
+                offset: 0,
+                limit: PAGE_SIZE,
+            }
+            .slice(&all_seeds)
+            .to_vec()
+        });
+
+        *found_seeds.lock().unwrap() = Some(FoundSeeds {
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            backend: None,
+            conditions: found_conditions,
+            seeds: Some(all_seeds),
+        });
+
+        ui_weak
+            .upgrade_in_event_loop(move |ui| {
+                ui.set_loading_seeds(false);
+
+                if let Some(seeds) = ranked {
                     let model = seeds
                         .iter()
-                        .map(|x| {
-                            ModelRc::new(VecModel::from(vec![StandardListViewItem::from(
-                                SharedString::from(x.to_string()),
-                            )]))
-                        })
+                        .map(seed_row)
                         .collect::<Vec<ModelRc<StandardListViewItem>>>();
 
                     ui.set_found_seeds(ModelRc::new(VecModel::from(model)));
-                    ui.set_loading_seeds(false);
-                })
-                .unwrap();
-        }
+                }
+
+                match res {
+                    // Stopping keeps the seeds found so far
+                    Ok(()) | Err(Error::Stopped) => {}
+                    Err(err) => dialog::show_message(
+                        format!("Could not search seeds: {err}"),
+                        parent_position,
+                        parent_size,
+                    ),
+                }
+            })
+            .unwrap();
     });
 }
 
+/// Returns the row of the seeds table showing a seed with its score
+/// and the numbers of the soft conditions it matches.
+fn seed_row(seed: &ScoredSeed) -> ModelRc<StandardListViewItem> {
+    let matched = seed
+        .matched
+        .iter()
+        .map(|x| (x + 1).to_string())
+        .collect::<Vec<String>>();
+
+    ModelRc::new(VecModel::from(vec![
+        StandardListViewItem::from(SharedString::from(seed.seed.to_string())),
+        StandardListViewItem::from(SharedString::from(seed.score.to_string())),
+        StandardListViewItem::from(SharedString::from(matched.join(", "))),
+    ]))
+}
+
+/// Shows why seeds cannot be found for these modes and conditions, if they cannot.
+fn can_find(
+    is_randomizer: bool,
+    is_bravery: bool,
+    conditions: &Mutex<Vec<Expression>>,
+    parent_position: PhysicalPosition,
+    parent_size: PhysicalSize,
+) -> bool {
+    if !is_randomizer && !is_bravery {
+        dialog::show_message(
+            String::from("Please select at least the Randomizer or Bravery game modes."),
+            parent_position,
+            parent_size,
+        );
+
+        return false;
+    }
+
+    if conditions.lock().unwrap().is_empty() {
+        dialog::show_message(
+            String::from("Please add at least 1 condition."),
+            parent_position,
+            parent_size,
+        );
+
+        return false;
+    }
+
+    true
+}
+
 fn generate_db(
     ui_weak: Weak<AppWindow>,
     parent_position: PhysicalPosition,
     parent_size: PhysicalSize,
 ) {
     ui_weak
-        .upgrade_in_event_loop(move |_| {
+        .upgrade_in_event_loop(move |ui| {
+            let database = selected_database(&ui);
+
+            let seeds = match selected_seeds(&ui) {
+                Ok(seeds) => seeds,
+                Err(err) => {
+                    dialog::show_message(err, parent_position, parent_size);
+                    return;
+                }
+            };
+
             let dialog = ProgressDialog::new().unwrap();
             let dialog_weak = dialog.as_weak();
             let stop_signal = Arc::new(AtomicBool::new(false));
@@ -118,17 +533,88 @@ fn generate_db(
             dialog.show().unwrap();
 
             thread::spawn(move || {
-                let res = db::generate(dialog_weak, parent_position, parent_size, stop_signal);
+                let progress_weak = dialog_weak.clone();
+                let stopped = stop_signal.clone();
 
-                if let Err(_) = res {
-                    fs::remove_file("./seeds.db").ok();
-                    fs::remove_file("./seeds.db-journal").ok();
+                // Resumed if the database was being generated with the same seeds
+                let res = backend::open(&database).and_then(|backend| {
+                    backend.generate(
+                        seeds,
+                        &move |done, total| {
+                            progress_weak
+                                .upgrade_in_event_loop(move |dialog| {
+                                    dialog.set_progress(done as f32 / total as f32);
+                                    dialog.set_progress_text((done * 100 / total) as i32);
+                                })
+                                .unwrap();
+                        },
+                        stop_signal,
+                    )
+                });
+
+                // Cancelled: the database is kept and will be resumed later on
+                if res.is_err() && stopped.load(Ordering::Relaxed) {
+                    return;
                 }
+
+                let message = match res {
+                    Ok(()) => String::from("Database generated with success!"),
+                    Err(err) => format!(
+                        "Could not create the database: {err}\nIt will resume from where it stopped next time."
+                    ),
+                };
+
+                dialog_weak
+                    .upgrade_in_event_loop(move |dialog| {
+                        dialog.hide().unwrap();
+                        dialog::show_message(message, parent_position, parent_size);
+                    })
+                    .unwrap();
             });
         })
         .unwrap();
 }
 
+/// Returns the path of the database selected in the seed finder.
+pub fn selected_database(ui: &AppWindow) -> PathBuf {
+    let name = ui
+        .get_databases()
+        .row_data(ui.get_database() as usize)
+        .map_or(String::from(db::DEFAULT_DATABASE), |x| x.text.to_string());
+
+    db::database_path(&name)
+}
+
+/// Returns the range of seeds entered in the seed finder, to generate or search.
+pub fn selected_seeds(ui: &AppWindow) -> Result<RangeInclusive<u32>, String> {
+    let parse = |text: SharedString, name: &str| {
+        text.trim()
+            .parse::<u32>()
+            .map_err(|_| format!("The {name} seed must be a number from 0 to {}.", u32::MAX))
+    };
+
+    let first = parse(ui.get_first_seed(), "first")?;
+    let last = parse(ui.get_last_seed(), "last")?;
+
+    if first > last {
+        return Err(String::from(
+            "The first seed must not be greater than the last one.",
+        ));
+    }
+
+    Ok(first..=last)
+}
+
+/// Returns the databases to choose from in the seed finder.
+pub fn init_databases() -> ModelRc<StandardListViewItem> {
+    let databases = db::databases()
+        .into_iter()
+        .map(|x| StandardListViewItem::from(SharedString::from(x)))
+        .collect::<Vec<StandardListViewItem>>();
+
+    ModelRc::new(VecModel::from(databases))
+}
+
 fn close_dialog(dialog_weak: Weak<ProgressDialog>, stop_signal: Arc<AtomicBool>) -> impl FnMut() {
     move || {
         dialog_weak.upgrade().unwrap().hide().unwrap();
@@ -1,29 +1,39 @@
 use std::{
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     thread,
 };
 
 use slint::{ComponentHandle, Model, ModelRc, SharedString, StandardListViewItem, VecModel, Weak};
 
 use crate::{
+    seed_finder::{
+        enums::{effect::Effect, expression::Expression, value::Value},
+        export::{self, Format},
+        score::SoftCondition,
+    },
     structs::game::Game,
     ui::{
-        condition,
-        enums::{condition::Condition, effect::Effect, value::Value},
-        finder, seed_info,
+        condition, dialog,
+        finder::{self, FoundSeeds},
+        seed_info,
         types::AppWindow,
     },
 };
 
 pub fn find_modes(
     ui_weak: Weak<AppWindow>,
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
 ) -> impl FnMut(bool, bool, bool) {
     move |is_randomizer, is_bravery, is_relic| {
         let ui = ui_weak.unwrap();
         let conditions = conditions.clone();
 
         *conditions.lock().unwrap() = vec![];
+        *soft_conditions.lock().unwrap() = vec![];
 
         ui.set_conditions_display(ModelRc::new(VecModel::from(vec![])));
         ui.set_values(condition::init_values(is_randomizer, is_bravery, is_relic));
@@ -35,18 +45,86 @@ pub fn find_modes(
 
 pub fn find(
     ui_weak: Weak<AppWindow>,
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+    stop_signal: Arc<AtomicBool>,
 ) -> impl FnMut(bool, bool, bool) {
     move |is_randomizer, is_bravery, is_relic| {
         let ui = ui_weak.unwrap();
         let conditions = conditions.clone();
+        let soft_conditions = soft_conditions.clone();
+        let found_seeds = found_seeds.clone();
+
+        if ui.get_without_database() {
+            let seeds = match finder::selected_seeds(&ui) {
+                Ok(seeds) => seeds,
+                Err(err) => {
+                    dialog::show_message(err, ui.window().position(), ui.window().size());
+                    return;
+                }
+            };
+
+            finder::search(
+                ui_weak.clone(),
+                seeds,
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                conditions,
+                soft_conditions,
+                found_seeds,
+                stop_signal.clone(),
+                ui.window().position(),
+                ui.window().size(),
+            );
+
+            return;
+        }
 
         finder::find(
             ui_weak.clone(),
+            finder::selected_database(&ui),
             is_randomizer,
             is_bravery,
             is_relic,
             conditions,
+            soft_conditions,
+            found_seeds,
+            ui.window().position(),
+            ui.window().size(),
+        );
+    }
+}
+
+pub fn load_seeds(
+    ui_weak: Weak<AppWindow>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+) -> impl FnMut(i32) {
+    move |offset| {
+        let ui = ui_weak.unwrap();
+
+        finder::load_seeds(
+            ui_weak.clone(),
+            found_seeds.clone(),
+            offset as u32,
+            ui.window().position(),
+            ui.window().size(),
+        );
+    }
+}
+
+pub fn export_seeds(
+    ui_weak: Weak<AppWindow>,
+    found_seeds: Arc<Mutex<Option<FoundSeeds>>>,
+) -> impl FnMut(bool) {
+    move |is_json| {
+        let ui = ui_weak.unwrap();
+
+        finder::export_seeds(
+            ui_weak.clone(),
+            found_seeds.clone(),
+            if is_json { Format::Json } else { Format::Csv },
             ui.window().position(),
             ui.window().size(),
         );
@@ -55,9 +133,9 @@ pub fn find(
 
 pub fn add_condition(
     ui_weak: Weak<AppWindow>,
-    conditions: Arc<Mutex<Vec<Condition>>>,
-) -> impl FnMut(i32, i32) {
-    move |item1, item2| {
+    conditions: Arc<Mutex<Vec<Expression>>>,
+) -> impl FnMut(i32, i32, bool, bool) {
+    move |item1, item2, is_negated, is_or| {
         let ui = ui_weak.unwrap();
         let conditions = conditions.clone();
 
@@ -73,23 +151,69 @@ pub fn add_condition(
             ui.get_conditions_display(),
             value,
             effect,
+            is_negated,
+            is_or,
+            is_randomizer,
+            is_bravery,
+            is_relic,
+            ui.window().position(),
+            ui.window().size(),
+        );
+    }
+}
+
+pub fn add_soft_condition(
+    ui_weak: Weak<AppWindow>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
+) -> impl FnMut(i32, i32, bool, i32, bool) {
+    move |item1, item2, is_negated, weight, is_per_match| {
+        let ui = ui_weak.unwrap();
+        let soft_conditions = soft_conditions.clone();
+
+        let is_randomizer = ui.get_is_randomizer_finder();
+        let is_bravery = ui.get_is_bravery_finder();
+        let is_relic = ui.get_is_relic_finder();
+
+        let value = Value::from((item1 as u32, is_randomizer, is_bravery));
+        let effect = Effect::from((item2 as u32, is_randomizer, is_bravery, is_relic));
+
+        condition::add_soft_condition(
+            soft_conditions,
+            ui.get_conditions_display(),
+            value,
+            effect,
+            is_negated,
+            weight,
+            is_per_match,
             is_randomizer,
             is_bravery,
+            is_relic,
             ui.window().position(),
             ui.window().size(),
         );
     }
 }
 
+pub fn stop_find(stop_signal: Arc<AtomicBool>) -> impl FnMut() {
+    move || stop_signal.store(true, Ordering::Relaxed)
+}
+
 pub fn remove_condition(
     ui_weak: Weak<AppWindow>,
-    conditions: Arc<Mutex<Vec<Condition>>>,
+    conditions: Arc<Mutex<Vec<Expression>>>,
+    soft_conditions: Arc<Mutex<Vec<SoftCondition>>>,
 ) -> impl FnMut(i32) {
     move |row| {
         let ui = ui_weak.unwrap();
         let conditions = conditions.clone();
+        let soft_conditions = soft_conditions.clone();
 
-        condition::remove_condition(conditions, ui.get_conditions_display(), row);
+        condition::remove_condition(
+            conditions,
+            soft_conditions,
+            ui.get_conditions_display(),
+            row,
+        );
     }
 }
 
@@ -128,6 +252,27 @@ pub fn area(ui_weak: Weak<AppWindow>, game: Arc<Mutex<Option<Game>>>) -> impl Fn
     }
 }
 
+pub fn export_game(ui_weak: Weak<AppWindow>, game: Arc<Mutex<Option<Game>>>) -> impl FnMut(bool) {
+    move |is_json| {
+        let ui = ui_weak.unwrap();
+        let format = if is_json { Format::Json } else { Format::Csv };
+
+        let message = match &*game.lock().unwrap() {
+            Some(game) => {
+                let path = export::default_path(&format!("seed-{}", game.seed), format);
+
+                match export::export_game(&path, format, game) {
+                    Ok(()) => format!("Seed exported to {}", path.display()),
+                    Err(err) => format!("Could not export the seed: {err}"),
+                }
+            }
+            None => String::from("Please enter a valid seed first."),
+        };
+
+        dialog::show_message(message, ui.window().position(), ui.window().size());
+    }
+}
+
 pub fn seed(
     ui_weak: Weak<AppWindow>,
     game: Arc<Mutex<Option<Game>>>,
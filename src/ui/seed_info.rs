@@ -4,6 +4,7 @@ use slint::{ComponentHandle, Image, SharedPixelBuffer, SharedString, Weak};
 
 use crate::{
     data::{DISPLAY, GAME_DATA},
+    seed_finder::bad_seeds,
     structs::{game::Game, game_manager, modes::bravery::Shift, monster::EMonster},
     ui::{
         dialog,
@@ -12,6 +13,7 @@ use crate::{
 };
 
 /// Updates all monster and relic displays.
+#[allow(clippy::too_many_arguments)]
 pub fn update_displays(
     ui_weak: Weak<AppWindow>,
     game: Arc<Mutex<Option<Game>>>,
@@ -31,12 +33,13 @@ pub fn update_displays(
         *game = None;
         *is_max_seed = false;
         clear_displays(&ui_weak, true, true, true);
+        update_seed_warning(&ui_weak, None);
         return;
     }
 
-    // Maximum: 999 999
-    if seed_str.len() > 6 {
-        seed_str = SharedString::from("999999");
+    // Maximum: 4 294 967 295
+    if seed_str.chars().all(|x| x.is_ascii_digit()) && seed_str.parse::<u32>().is_err() {
+        seed_str = SharedString::from(u32::MAX.to_string());
 
         let seed_str = seed_str.clone();
         ui_weak
@@ -44,14 +47,15 @@ pub fn update_displays(
             .unwrap();
 
         // Prevents generating the same game over and over again
-        if is_max_seed.to_owned() == true {
+        if is_max_seed.to_owned() {
             return;
         }
     }
 
     // Check if seed is a valid u32
     if let Ok(seed) = seed_str.parse::<u32>() {
-        *is_max_seed = seed == 999999;
+        *is_max_seed = seed == u32::MAX;
+        update_seed_warning(&ui_weak, Some(seed));
 
         if !is_randomizer && !is_bravery && !is_relic {
             *game = None;
@@ -59,23 +63,25 @@ pub fn update_displays(
             return;
         }
 
-        let new_game = game_manager::generate_game(seed, is_randomizer, is_bravery, is_relic);
-
-        if new_game.is_bad_seed() {
-            *game = None;
-            clear_displays(&ui_weak, true, true, true);
-
-            ui_weak
-                .upgrade_in_event_loop(move |ui| {
-                    dialog::show_message(
-                        format!("Error: Seed {seed} is invalid in Randomizer + Bravery modes. The game will fail to generate this seed, and it cannot be used."),
-                        ui.window().position(),
-                        ui.window().size(),
-                    )
-                })
-                .unwrap();
-            return;
-        }
+        let new_game = match game_manager::generate_game(seed, is_randomizer, is_bravery, is_relic)
+        {
+            Ok(new_game) => new_game,
+            Err(error) => {
+                *game = None;
+                clear_displays(&ui_weak, true, true, true);
+
+                ui_weak
+                    .upgrade_in_event_loop(move |ui| {
+                        dialog::show_message(
+                            format!("Error: Seed {seed} is invalid in these game modes: {error}. The game will fail to generate this seed, and it cannot be used."),
+                            ui.window().position(),
+                            ui.window().size(),
+                        )
+                    })
+                    .unwrap();
+                return;
+            }
+        };
 
         // Don't update if we call `update_eggs_ui` and set the area to 0
         let mut should_update_relic = area_id != 0;
@@ -120,6 +126,7 @@ pub fn update_displays(
         *game = None;
         *is_max_seed = false;
         clear_displays(&ui_weak, true, true, true);
+        update_seed_warning(&ui_weak, None);
 
         ui_weak
             .upgrade_in_event_loop(move |ui| ui.set_seed(SharedString::from("")))
@@ -185,7 +192,7 @@ pub fn update_randomizer_ui(ui_weak: &Weak<AppWindow>, game: &Game, area_id: u32
             })
             .unwrap();
     } else {
-        clear_displays(&ui_weak, true, false, false);
+        clear_displays(ui_weak, true, false, false);
     }
 }
 
@@ -269,6 +276,27 @@ pub fn update_relics_ui(ui_weak: &Weak<AppWindow>, game: &Game, area_id: u32) {
     }
 }
 
+/// Warns about a seed that freezes the game in a Bravery mode, or clears the warning.
+fn update_seed_warning(ui_weak: &Weak<AppWindow>, seed: Option<u32>) {
+    let warning = seed
+        .map(bad_seeds::check)
+        .unwrap_or_default()
+        .iter()
+        .map(|x| {
+            format!(
+                "Warning: this seed freezes the game in {} modes ({}), do not use it with them.",
+                x.modes(),
+                x.error()
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    ui_weak
+        .upgrade_in_event_loop(move |ui| ui.set_seed_warning(SharedString::from(warning)))
+        .unwrap();
+}
+
 /// Clears the specified monster displays and/or the relic display.
 pub fn clear_displays(ui_weak: &Weak<AppWindow>, randomizer: bool, bravery: bool, relic: bool) {
     ui_weak
@@ -0,0 +1,55 @@
+mod common;
+
+use std::sync::{atomic::AtomicBool, Arc};
+
+use common::TempDir;
+use sanctuary_seeder::seed_finder::{bad_seeds, db};
+
+#[test]
+fn bad_seeds() {
+    let dir = TempDir::new("bad-seeds");
+    let path = dir.path().join("seeds.db");
+
+    // Bad seeds are only stored in a generated database
+    assert!(bad_seeds::scan(&path, 0..=999, |_, _| {}, Arc::new(AtomicBool::new(false))).is_err());
+    assert!(!path.exists());
+
+    db::generate(&path, 0..=999, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    // Seed 32410 fails with the Randomizer and Bravery modes
+    let found = bad_seeds::scan(
+        &path,
+        31000..=33999,
+        |_, _| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert!(found
+        .iter()
+        .any(|x| x.seed == 32410 && x.is_randomizer && x.draws > 0));
+    assert!(found.iter().all(|x| bad_seeds::check(x.seed).contains(x)));
+    assert!(bad_seeds::check(0).is_empty());
+
+    assert_eq!(bad_seeds::stored(&path, 0..=u32::MAX).unwrap(), found);
+    assert_eq!(
+        bad_seeds::stored(&path, 32410..=32410).unwrap(),
+        bad_seeds::check(32410)
+    );
+
+    // Scanning again replaces the bad seeds of the range, and keeps the others
+    bad_seeds::scan(
+        &path,
+        32000..=32999,
+        |_, _| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert_eq!(bad_seeds::stored(&path, 0..=u32::MAX).unwrap(), found);
+
+    // The catalogue is kept alongside the seeds
+    db::generate(&path, 0..=1999, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    assert_eq!(bad_seeds::stored(&path, 0..=u32::MAX).unwrap(), found);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{Query, TempDir};
+use rusqlite::Connection;
+use sanctuary_seeder::seed_finder::{
+    backend,
+    bitmap::{BitmapIndex, BRAVERY_GAMES},
+    enums::expression::Expression,
+};
+
+#[test]
+fn bitmap() {
+    let dir = TempDir::new("bitmap");
+    let path = dir.path().join("seeds.db");
+    let backend = backend::open(&path).unwrap();
+
+    common::generate(backend.as_ref());
+
+    let mut index = BitmapIndex::open(&path).unwrap().unwrap();
+    let vaero = "Vaero:available".parse::<Expression>().unwrap();
+    let seeds = index
+        .evaluate(&vaero, Some(BRAVERY_GAMES[0]), None)
+        .unwrap()
+        .unwrap();
+
+    assert!(!seeds.is_empty() && seeds.count() < 2000);
+    assert!(seeds.seeds().all(|x| seeds.contains(x)));
+
+    // Only availability conditions have bitmaps
+    let starter = "Vaero:starter".parse::<Expression>().unwrap();
+    assert!(index
+        .evaluate(&starter, Some(BRAVERY_GAMES[0]), None)
+        .unwrap()
+        .is_none());
+
+    let queries: [Query; 4] = [
+        (false, true, false, "Vaero:available & !Tanuki:available"),
+        (true, true, true, "Vaero:available | Sun Ring:available"),
+        (
+            true,
+            true,
+            false,
+            "Vaero:available & Tanuki:egg:Mountain Path",
+        ),
+        (true, false, true, "!Sun Ring:available"),
+    ];
+
+    let with_bitmaps = queries.map(|x| common::find(backend.as_ref(), x));
+
+    // Without bitmaps, seeds are found from the rows only
+    Connection::open(&path)
+        .unwrap()
+        .execute("DELETE FROM Bitmap", ())
+        .unwrap();
+
+    assert!(BitmapIndex::open(&path).unwrap().is_none());
+    assert_eq!(
+        with_bitmaps,
+        queries.map(|x| common::find(backend.as_ref(), x))
+    );
+}
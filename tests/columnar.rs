@@ -0,0 +1,61 @@
+#![cfg(feature = "columnar")]
+
+mod common;
+
+use std::sync::{atomic::AtomicBool, Arc};
+
+use common::{Query, TempDir, SEEDS};
+use sanctuary_seeder::seed_finder::{backend, verify};
+
+#[test]
+fn columnar() {
+    let dir = TempDir::new("columnar");
+    let sqlite = backend::open(&dir.path().join("seeds.db")).unwrap();
+    let columnar = backend::open(&dir.path().join("seeds.idx")).unwrap();
+
+    for backend in [&sqlite, &columnar] {
+        common::generate(backend.as_ref());
+
+        assert!(backend.is_complete().unwrap());
+    }
+
+    let mismatches = verify::verify(
+        columnar.as_ref(),
+        SEEDS,
+        1,
+        |_, _| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert!(mismatches.is_empty());
+
+    let queries: [Query; 4] = [
+        (true, true, true, "Vaero:starter | Sun Ring:area:Blue Caves"),
+        (
+            false,
+            true,
+            false,
+            "Tanuki:egg:Mountain Path:light & !Koi:army",
+        ),
+        (
+            false,
+            true,
+            true,
+            "any monster:army:dark | Sun Ring:scene:MountainPath_West2",
+        ),
+        (
+            true,
+            false,
+            true,
+            "Catzerker:replacement:Vaero | Sun Ring:available",
+        ),
+    ];
+
+    for query in queries {
+        let seeds = common::find(columnar.as_ref(), query);
+
+        assert!(!seeds.is_empty());
+        assert_eq!(seeds, common::find(sqlite.as_ref(), query), "{}", query.3);
+    }
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use std::{
+    env, fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+use sanctuary_seeder::seed_finder::{backend::Backend, enums::expression::Expression};
+
+/// Seeds generated by the tests sharing this fixture.
+pub const SEEDS: RangeInclusive<u32> = 0..=1999;
+
+/// The game modes of a query, then its conditions.
+pub type Query = (bool, bool, bool, &'static str);
+
+/// A temporary directory for the databases of a test, deleted once dropped,
+/// even if the test fails.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        TempDir(env::temp_dir().join(format!("sanctuary-seeder-{name}-{}", std::process::id())))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+/// Generates `SEEDS` in a database.
+pub fn generate(backend: &dyn Backend) {
+    backend
+        .generate(SEEDS, &|_, _| {}, Arc::new(AtomicBool::new(false)))
+        .unwrap();
+}
+
+/// Returns the conditions of a query, to pass to the seed finder.
+pub fn conditions(query: &str) -> Arc<Mutex<Vec<Expression>>> {
+    Arc::new(Mutex::new(vec![query.parse::<Expression>().unwrap()]))
+}
+
+/// Returns the seeds matching a query.
+pub fn find(
+    backend: &dyn Backend,
+    (is_randomizer, is_bravery, is_relic, query): Query,
+) -> Vec<u32> {
+    backend
+        .find_seeds(is_randomizer, is_bravery, is_relic, conditions(query))
+        .unwrap()
+}
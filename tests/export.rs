@@ -0,0 +1,89 @@
+mod common;
+
+use std::fs;
+
+use common::TempDir;
+use sanctuary_seeder::{
+    seed_finder::{
+        backend,
+        export::{self, Format},
+        score::{self, SoftCondition},
+    },
+    structs::{game::Game, game_manager},
+};
+
+#[test]
+fn export() {
+    let dir = TempDir::new("export");
+    let dir = dir.path();
+    let backend = backend::open(&dir.join("seeds.db")).unwrap();
+
+    common::generate(backend.as_ref());
+
+    let soft_conditions = ["2=Koi:swimming".parse::<SoftCondition>().unwrap()];
+    let seeds = score::find_seeds(
+        backend.as_ref(),
+        true,
+        true,
+        true,
+        common::conditions("Vaero:available"),
+        &soft_conditions,
+        None,
+    )
+    .unwrap();
+
+    assert!(!seeds.is_empty());
+
+    // Rows read from the database are the same as generated ones
+    for format in [Format::Csv, Format::Json] {
+        let read = dir.join(format!("read.{}", format.extension()));
+        let generated = dir.join(format!("generated.{}", format.extension()));
+
+        export::export_seeds(
+            &read,
+            format,
+            &seeds,
+            true,
+            true,
+            true,
+            Some(backend.as_ref()),
+        )
+        .unwrap();
+        export::export_seeds(&generated, format, &seeds, true, true, true, None).unwrap();
+
+        assert_eq!(fs::read(&read).unwrap(), fs::read(&generated).unwrap());
+    }
+
+    let csv = fs::read_to_string(dir.join("read.csv")).unwrap();
+    assert_eq!(csv.lines().count(), seeds.len() + 1);
+    assert!(csv.starts_with("Seed,Score,Preferred,Randomizer."));
+
+    let json = fs::read_to_string(dir.join("read.json")).unwrap();
+    let records = serde_json::from_str::<Vec<serde_json::Value>>(&json).unwrap();
+    assert_eq!(records.len(), seeds.len());
+    assert_eq!(records[0]["Seed"], seeds[0].seed);
+    assert_eq!(records[0]["Score"], 2);
+    assert_eq!(records[0]["Bravery.Swimming"], "Koi");
+    assert!(records[0]["Relic.MountainPath"].is_string());
+
+    // Games are exported with their serde derives
+    let game = game_manager::generate_game(seeds[0].seed, true, true, true).unwrap();
+    let path = dir.join("game.json");
+
+    export::export_game(&path, Format::Json, &game).unwrap();
+    let exported = serde_json::from_str::<Game>(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(exported == game);
+
+    let path = dir.join("game.csv");
+
+    export::export_game(&path, Format::Csv, &game).unwrap();
+    let csv = fs::read_to_string(&path).unwrap();
+    assert!(csv.contains(&format!("\nseed,{}\n", game.seed)));
+    assert!(csv.contains("\nbravery_data.starters.1,"));
+
+    assert!(Format::from_path(&dir.join("seeds.txt")).is_err());
+    assert_eq!(
+        Format::from_path(&dir.join("seeds.JSON")).unwrap(),
+        Format::Json
+    );
+}
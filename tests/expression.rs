@@ -0,0 +1,96 @@
+use sanctuary_seeder::seed_finder::enums::expression::Expression;
+
+#[test]
+fn expression() {
+    let expression = "Vaero:starter | Kanko:starter & !(Tanuki:available | Koi:army)"
+        .parse::<Expression>()
+        .unwrap();
+
+    assert_eq!(
+        expression.to_string(),
+        "Vaero is a starter OR (Kanko is a starter AND NOT (Tanuki is available OR Koi is in the Bravery Monster Army))"
+    );
+
+    let mut params = vec![];
+    let sql = expression.to_sql(&mut params);
+
+    assert!(sql
+        .starts_with("(? IN (Start1,Start2)) OR ((? IN (Start1,Start2)) AND (NOT IFNULL(((? IN ("));
+    assert!(sql.ends_with("? IN (Army1,Army2,Army3,Army4,Army5,Army6,Army7))), 0)))"));
+    assert_eq!(params, vec![7, 51, 50, 49]);
+
+    assert!("Vaero:starter |".parse::<Expression>().is_err());
+    assert!("(Vaero:starter".parse::<Expression>().is_err());
+    assert!("Vaero:starter)".parse::<Expression>().is_err());
+}
+
+#[test]
+fn group() {
+    let expression = "any Dragon:starter & !any Ignite:army"
+        .parse::<Expression>()
+        .unwrap();
+
+    assert_eq!(
+        expression.to_string(),
+        "Any Dragon monster is a starter AND NOT Any Ignite monster is in the Bravery Monster Army"
+    );
+
+    // Spectral familiars can never be eggs
+    let expression = "any Spirit:egg:Mountain Path"
+        .parse::<Expression>()
+        .unwrap();
+
+    let mut params = vec![];
+    expression.to_sql(&mut params);
+
+    assert!(!params.contains(&0));
+    assert!("any Dragon:bex:Blue Caves".parse::<Expression>().is_err());
+    assert!("any Vaero:starter".parse::<Expression>().is_err());
+}
+
+#[test]
+fn shift() {
+    let expression = "Tanuki:egg:Mountain Path:light & any monster:army:dark"
+        .parse::<Expression>()
+        .unwrap();
+
+    assert_eq!(
+        expression.to_string(),
+        "Tanuki egg is in Mountain Path (Light) AND Any monster is a Dark egg in the Bravery Monster Army"
+    );
+
+    let mut params = vec![];
+    let sql = expression.to_sql(&mut params);
+
+    // Parameters are in the same order as in the query
+    assert!(sql.starts_with("(? IN (Bravery.MountainPath) AND (ShiftOffset + ?) % ? IN ("));
+    assert_eq!(params[..3], [50, 0, 32]);
+}
+
+#[test]
+fn relic_scene() {
+    let expression = "Sun Ring:scene:MountainPath_West2:19"
+        .parse::<Expression>()
+        .unwrap();
+
+    assert_eq!(
+        expression.to_string(),
+        "Sun Ring is in MountainPath_West2 (chest 19)"
+    );
+
+    let mut params = vec![];
+    let sql = expression.to_sql(&mut params);
+
+    assert_eq!(
+        sql,
+        "? IN (Relic.MountainPath) AND Relic.MountainPathScene = ? AND Relic.MountainPathChest = ?"
+    );
+    assert_eq!(params, [2200, 1, 19]);
+
+    assert!("Sun Ring:scene:MountainPath_West2:4"
+        .parse::<Expression>()
+        .is_err());
+    assert!("Vaero:scene:MountainPath_West2"
+        .parse::<Expression>()
+        .is_err());
+}
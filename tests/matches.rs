@@ -0,0 +1,70 @@
+use std::{
+    env, fs,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+use sanctuary_seeder::seed_finder::{db, enums::expression::Expression, search};
+
+#[test]
+fn matches() {
+    let dir = env::temp_dir().join(format!("sanctuary-seeder-matches-{}", std::process::id()));
+    let path = dir.join("seeds.db");
+
+    db::generate(&path, 0..=999, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    let queries = [
+        "Vaero:available & !Koi:army",
+        "Spectral Toad:familiar | Magmapillar:bex | Nautilid:swimming",
+        "Kanko:cryomancer | Dracogran:cryomancerrequired | Vasuki:endoftime",
+        "Vaero:chest & !Vaero:starter",
+        "Yowie:egg:Mountain Path | Vaero:egg:Mountain Path:dark",
+        "any monster:egg:Blue Caves:light & any monster:army:dark",
+        "Koi:army:light | any Dragon:starter",
+        "Vaero:area:Mountain Path | Catzerker:replacement:Vaero",
+        "Sun Ring:available & !Sun Ring:area:Blue Caves",
+        "Sun Ring:scene:MountainPath_Center7 | Sun Ring:scene:MountainPath_WestHidden:0",
+    ];
+
+    let mut found = 0;
+
+    for modes in 1..8 {
+        let (is_randomizer, is_bravery, is_relic) =
+            (modes & 1 != 0, modes & 2 != 0, modes & 4 != 0);
+        let games = (0..=999)
+            .filter_map(|x| search::generate_game(x, is_randomizer, is_bravery, is_relic).ok())
+            .collect::<Vec<_>>();
+
+        for query in queries {
+            let expression = query.parse::<Expression>().unwrap();
+
+            if expression
+                .validate(is_randomizer, is_bravery, is_relic)
+                .is_some()
+            {
+                continue;
+            }
+
+            let seeds = games
+                .iter()
+                .filter(|x| expression.matches(x))
+                .map(|x| x.seed)
+                .collect::<Vec<u32>>();
+
+            let expected = db::find_seeds(
+                &path,
+                is_randomizer,
+                is_bravery,
+                is_relic,
+                Arc::new(Mutex::new(vec![expression])),
+            )
+            .unwrap();
+
+            assert_eq!(seeds, expected, "{query} ({modes})");
+            found += seeds.len();
+        }
+    }
+
+    assert!(found > 0);
+
+    fs::remove_dir_all(dir).unwrap();
+}
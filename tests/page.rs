@@ -0,0 +1,74 @@
+mod common;
+
+use common::{Query, TempDir};
+use rusqlite::Connection;
+use sanctuary_seeder::seed_finder::backend::{self, Backend, Page};
+
+#[test]
+fn page() {
+    let dir = TempDir::new("page");
+    let path = dir.path().join("seeds.db");
+    let backend = backend::open(&path).unwrap();
+
+    common::generate(backend.as_ref());
+
+    // Found from bitmaps only, from bitmaps and rows, and from rows only
+    let queries: [Query; 3] = [
+        (true, true, false, "Vaero:available & !Tanuki:available"),
+        (true, true, false, "Vaero:available & !Vaero:starter"),
+        (
+            false,
+            true,
+            true,
+            "Vaero:starter | Sun Ring:area:Blue Caves",
+        ),
+    ];
+
+    let pages = [(0, 10), (5, 20), (0, u32::MAX), (1990, 100), (3, 0)]
+        .map(|(offset, limit)| Page { offset, limit });
+
+    let check = |backend: &dyn Backend| {
+        for query in queries {
+            let (is_randomizer, is_bravery, is_relic, text) = query;
+            let seeds = common::find(backend, query);
+
+            assert!(seeds.len() > 20, "{text}");
+            assert_eq!(
+                backend
+                    .count_seeds(
+                        is_randomizer,
+                        is_bravery,
+                        is_relic,
+                        common::conditions(text)
+                    )
+                    .unwrap(),
+                seeds.len() as u64,
+                "{text}"
+            );
+
+            for page in pages {
+                let found = backend
+                    .find_seeds_page(
+                        is_randomizer,
+                        is_bravery,
+                        is_relic,
+                        common::conditions(text),
+                        page,
+                    )
+                    .unwrap();
+
+                assert_eq!(found, page.slice(&seeds), "{text}");
+            }
+        }
+    };
+
+    check(backend.as_ref());
+
+    // Without bitmaps, pages are found from the rows only
+    Connection::open(&path)
+        .unwrap()
+        .execute("DELETE FROM Bitmap", ())
+        .unwrap();
+
+    check(backend.as_ref());
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use std::{
+    cell::Cell,
+    ops::RangeInclusive,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use common::{TempDir, SEEDS};
+use rusqlite::{types::Value, Connection};
+use sanctuary_seeder::seed_finder::db;
+
+#[test]
+fn resume() {
+    let dir = TempDir::new("resume");
+    let path = dir.path().join("seeds.db");
+
+    db::generate(&path, SEEDS, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    // Without bitmaps, the rows are counted to know whether every batch is committed
+    let conn = Connection::open(&path).unwrap();
+    conn.execute("DELETE FROM Bitmap", ()).unwrap();
+
+    // A seed failing with the Bravery mode alone, but not with the Randomizer and Bravery modes
+    conn.execute_batch(
+        "DELETE FROM Bravery WHERE Id = 1234 * 2; DELETE FROM Relic WHERE Id = 1234 * 3 + 1;",
+    )
+    .unwrap();
+
+    assert!(db::is_complete(&path).unwrap());
+
+    // Generating again keeps the seeds already committed
+    db::generate(&path, SEEDS, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    let rows = |table: &str, id: i64| {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {table} WHERE Id = ?"),
+            [id],
+            |row| row.get::<usize, i64>(0),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(rows("Bravery", 1234 * 2), 0);
+    assert_eq!(rows("Bravery", 1234 * 2 + 1), 1);
+
+    // A Relic row without its Bravery row cannot come from a failed game
+    conn.execute_batch("DELETE FROM Bitmap; DELETE FROM Relic WHERE Id = 1235 * 3 + 1;")
+        .unwrap();
+
+    assert!(!db::is_complete(&path).unwrap());
+
+    // As many rows as a seed failing with the Bravery mode alone, but not from the same seed
+    conn.execute("DELETE FROM Bravery WHERE Id = 1236 * 2", ())
+        .unwrap();
+
+    assert!(!db::is_complete(&path).unwrap());
+}
+
+#[test]
+fn stop() {
+    const SEEDS: RangeInclusive<u32> = 0..=4999;
+
+    let dir = TempDir::new("stop");
+    let path = dir.path().join("seeds.db");
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // Stopped after the second batch, while the next ones may be written already
+    let res = db::generate(
+        &path,
+        SEEDS,
+        |done, _| {
+            if done == 2 {
+                stop.store(true, Ordering::Relaxed);
+            }
+        },
+        stop.clone(),
+    );
+
+    assert!(res.is_err());
+    assert!(!db::is_complete(&path).unwrap());
+
+    let batches = Connection::open(&path)
+        .unwrap()
+        .query_row(
+            "SELECT Value FROM Metadata WHERE Key = 'Batches'",
+            [],
+            |row| row.get::<usize, String>(0),
+        )
+        .unwrap()
+        .parse::<u32>()
+        .unwrap();
+
+    assert!((2..5).contains(&batches));
+
+    // Resumed from the batches committed
+    let start = Cell::new(None);
+
+    db::generate(
+        &path,
+        SEEDS,
+        |done, _| {
+            if start.get().is_none() {
+                start.set(Some(done));
+            }
+        },
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert_eq!(start.get(), Some(batches));
+    assert!(db::is_complete(&path).unwrap());
+
+    let uninterrupted = dir.path().join("uninterrupted.db");
+    db::generate(
+        &uninterrupted,
+        SEEDS,
+        |_, _| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    for table in ["Randomizer", "Bravery", "Relic", "Bitmap"] {
+        assert_eq!(rows(&path, table), rows(&uninterrupted, table), "{table}");
+    }
+}
+
+/// Returns every row of a table, ordered by the primary key.
+fn rows(path: &Path, table: &str) -> Vec<Vec<Value>> {
+    let conn = Connection::open(path).unwrap();
+    let mut stmt = conn
+        .prepare(&format!("SELECT * FROM {table} ORDER BY 1, 2"))
+        .unwrap();
+    let columns = stmt.column_count();
+
+    stmt.query_map([], |row| (0..columns).map(|x| row.get(x)).collect())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+
+use sanctuary_seeder::structs::{
+    game_manager,
+    map::Area,
+    monster::EMonster,
+    random::{Call, Rng, TracedCall},
+};
+use unity_random::Random;
+
+/// Replays the results of a trace, checking that the same calls are made.
+struct Replay(VecDeque<TracedCall>);
+
+impl Replay {
+    fn next(&mut self, expected: Call) -> Call {
+        let call = self.0.pop_front().unwrap().call;
+
+        match (&call, &expected) {
+            (Call::RangeInt { min, max, .. }, Call::RangeInt { min: a, max: b, .. })
+                if (min, max) == (a, b) => {}
+            (Call::RangeFloat { .. }, Call::RangeFloat { .. })
+            | (Call::Value { .. }, Call::Value { .. }) => {}
+            _ => panic!("expected {expected:?}, replayed {call:?}"),
+        }
+
+        call
+    }
+}
+
+impl Rng for Replay {
+    fn range_int(&mut self, min: i32, max: i32) -> i32 {
+        match self.next(Call::RangeInt {
+            min,
+            max,
+            result: 0,
+        }) {
+            Call::RangeInt { result, .. } => result,
+            _ => unreachable!(),
+        }
+    }
+
+    fn range_float(&mut self, min: f32, max: f32) -> f32 {
+        match self.next(Call::RangeFloat {
+            min,
+            max,
+            result: 0.,
+        }) {
+            Call::RangeFloat { result, .. } => result,
+            _ => unreachable!(),
+        }
+    }
+
+    fn value(&mut self) -> f32 {
+        match self.next(Call::Value { result: 0. }) {
+            Call::Value { result } => result,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Draws integers from Unity's generator, but ever increasing small floats.
+struct Increasing {
+    random: Random,
+    next: f32,
+}
+
+impl Rng for Increasing {
+    fn range_int(&mut self, min: i32, max: i32) -> i32 {
+        self.random.range_int(min, max)
+    }
+
+    fn range_float(&mut self, min: f32, max: f32) -> f32 {
+        self.next += 0.000_01;
+        min + (max - min) * self.next
+    }
+
+    fn value(&mut self) -> f32 {
+        self.range_float(0., 1.)
+    }
+}
+
+#[test]
+fn replay() {
+    for seed in [0, 12345, 32410] {
+        let (game, calls) = game_manager::generate_game_traced(seed, true, true, true);
+        let mut replay = Replay(calls.into());
+
+        let replayed = game_manager::generate_game_with(&mut replay, seed, true, true, true);
+
+        assert!(replay.0.is_empty());
+        assert!(game.as_ref().ok() == replayed.as_ref().ok());
+        assert_eq!(game.err(), replayed.err());
+    }
+}
+
+#[test]
+fn tanuki_fallback() {
+    // The starters of this seed let the first eggs meet the requirements
+    let mut random = Random::new();
+    random.init_state(12);
+
+    // Each rating beats the previous ones, then the Tanuki roll passes and beats them all,
+    // after which Tanuki is already an egg
+    let game =
+        game_manager::generate_game_with(Increasing { random, next: 0. }, 12, false, true, false)
+            .unwrap();
+
+    let eggs = game.bravery_data.unwrap().eggs;
+
+    assert_eq!(eggs[Area::MountainPath as usize], EMonster::Tanuki as u32);
+    assert_eq!(
+        eggs.iter()
+            .filter(|x| **x == EMonster::Tanuki as u32)
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,117 @@
+use std::{
+    env, fs,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+use sanctuary_seeder::seed_finder::{
+    backend,
+    enums::expression::Expression,
+    score::{self, SoftCondition},
+    search,
+};
+
+#[test]
+fn score() {
+    let dir = env::temp_dir().join(format!("sanctuary-seeder-score-{}", std::process::id()));
+    let backend = backend::open(&dir.join("seeds.db")).unwrap();
+
+    backend
+        .generate(0..=999, &|_, _| {}, Arc::new(AtomicBool::new(false)))
+        .unwrap();
+
+    let conditions = Arc::new(Mutex::new(vec!["Vaero:available"
+        .parse::<Expression>()
+        .unwrap()]));
+    let soft_conditions = [
+        "3=Vaero:starter",
+        "any monster:egg:Mountain Path:light",
+        "-20=any Dragon:army",
+        "2*=any monster:egg:Mountain Path:light | any monster:egg:Blue Caves:light \
+            | any monster:egg:Ancient Woods:light | any monster:egg:Stronghold Dungeon:light \
+            | any monster:egg:Snowy Peaks:light | any monster:egg:Sun Palace:light",
+    ]
+    .map(|x| x.parse::<SoftCondition>().unwrap());
+
+    let seeds = score::find_seeds(
+        backend.as_ref(),
+        false,
+        true,
+        false,
+        conditions.clone(),
+        &soft_conditions,
+        None,
+    )
+    .unwrap();
+
+    assert!(seeds.windows(2).all(|x| x[0].score >= x[1].score));
+    assert!(seeds[0].score >= 3 && seeds.iter().any(|x| x.score < 0));
+
+    // Light-shifted eggs each add the weight of the last soft condition
+    let eggs = |seed: &score::ScoredSeed| {
+        let score = seed
+            .matched
+            .iter()
+            .filter(|x| **x != 3)
+            .map(|x| soft_conditions[*x].weight);
+
+        (seed.score - score.sum::<i32>()) / 2
+    };
+
+    for seed in &seeds {
+        assert_eq!(eggs(seed) > 0, seed.matched.contains(&3));
+    }
+
+    assert!(seeds.iter().any(|x| eggs(x) > 1));
+
+    let mut found = vec![];
+
+    search::search(
+        0..=999,
+        false,
+        true,
+        false,
+        conditions,
+        &soft_conditions,
+        |_, _| {},
+        |seeds| found.extend_from_slice(seeds),
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    score::rank(&mut found);
+
+    let summary = |seeds: &[score::ScoredSeed]| {
+        seeds
+            .iter()
+            .map(|x| (x.seed, x.score, x.matched.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(summary(&seeds), summary(&found));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn soft_condition() {
+    let soft = "Vaero:starter".parse::<SoftCondition>().unwrap();
+    assert_eq!(soft.weight, 1);
+    assert_eq!(soft.to_string(), "Vaero is a starter (+1)");
+
+    let soft = "-2 = Koi:army".parse::<SoftCondition>().unwrap();
+    assert_eq!(soft.weight, -2);
+
+    let soft = "2 *= any Dragon:army".parse::<SoftCondition>().unwrap();
+    assert!(soft.weight == 2 && soft.is_per_match);
+    assert_eq!(
+        soft.to_string(),
+        "Any Dragon monster is in the Bravery Monster Army (+2 each)"
+    );
+
+    let soft = "*=any Dragon:army".parse::<SoftCondition>().unwrap();
+    assert!(soft.weight == 1 && soft.is_per_match);
+
+    assert!("a=Koi:army".parse::<SoftCondition>().is_err());
+    assert!("a*=Koi:army".parse::<SoftCondition>().is_err());
+    assert!("3=".parse::<SoftCondition>().is_err());
+}
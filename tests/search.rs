@@ -0,0 +1,44 @@
+use std::{
+    env, fs,
+    sync::{atomic::AtomicBool, Arc, Mutex},
+};
+
+use sanctuary_seeder::seed_finder::{db, enums::expression::Expression, search};
+
+#[test]
+fn search() {
+    let dir = env::temp_dir().join(format!("sanctuary-seeder-search-{}", std::process::id()));
+    let path = dir.join("seeds.db");
+
+    db::generate(&path, 0..=2999, |_, _| {}, Arc::new(AtomicBool::new(false))).unwrap();
+
+    let conditions = Arc::new(Mutex::new(vec![
+        "Vaero:available".parse::<Expression>().unwrap(),
+        "Tanuki:egg:Mountain Path | Sun Ring:area:Blue Caves"
+            .parse::<Expression>()
+            .unwrap(),
+    ]));
+
+    let mut found = vec![];
+
+    search::search(
+        0..=2999,
+        true,
+        true,
+        true,
+        conditions.clone(),
+        &[],
+        |_, _| {},
+        |seeds| found.extend(seeds.iter().map(|x| x.seed)),
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert!(!found.is_empty());
+    assert_eq!(
+        found,
+        db::find_seeds(&path, true, true, true, conditions).unwrap()
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -3,7 +3,13 @@ use std::{
     path::Path,
 };
 
-use sanctuary_seeder::structs::{game::Game, game_manager};
+use sanctuary_seeder::structs::{
+    error::GenerationError,
+    game::Game,
+    game_manager,
+    random::{Call, TracedCall},
+};
+use unity_random::{Random, State};
 
 #[test]
 fn seed() {
@@ -32,12 +38,68 @@ fn seed() {
 
         // Bad seeds - the game fails to generate, no need to check anything else
         // Seed 32410 is one of them.
-        if game.is_bad_seed() {
-            assert!(new_game.is_bad_seed());
+        if game.is_bravery && game.bravery_data.is_none() {
+            let error = new_game.err();
+
+            // Detected before the starters are ever rerolled
+            assert!(matches!(
+                error,
+                Some(GenerationError::BraveryEggs { tries, .. }) if tries < 100
+            ));
+            assert!(error.unwrap().draws() > 0);
         } else {
+            let new_game = new_game.ok().unwrap();
+
             assert_eq!(game.mapping, new_game.mapping);
             assert_eq!(game.bravery_data, new_game.bravery_data);
             assert_eq!(game.relics, new_game.relics);
         }
     }
 }
+
+#[test]
+fn negative_state() {
+    // Seeds above `i32::MAX` are negative in the game, and Unity initializes its state
+    // from their bits: the seed, then each previous value times 1812433253 plus 1
+    let states = [
+        (
+            i32::MAX as u32 + 1,
+            [0x80000000, 0x80000001, 0xEC078966, 0xF14ACB3F],
+        ),
+        (u32::MAX, [0xFFFFFFFF, 0x93F8769C, 0x66C4478D, 0x0695AFA2]),
+    ];
+
+    for (seed, [s0, s1, s2, s3]) in states {
+        let mut random = Random::new();
+        random.state = State { s0, s1, s2, s3 };
+
+        let game = game_manager::generate_game(seed, true, true, true);
+        let expected = game_manager::generate_game_with(random, seed, true, true, true);
+
+        match (game, expected) {
+            (Ok(game), Ok(expected)) => assert!(game == expected),
+            (game, expected) => assert_eq!(game.err(), expected.err()),
+        }
+    }
+}
+
+#[test]
+fn trace() {
+    let (game, calls) = game_manager::generate_game_traced(12345, true, true, true);
+
+    assert!(game.ok() == game_manager::generate_game(12345, true, true, true).ok());
+    assert!(calls.iter().all(|x| !x.stage.is_empty()));
+    assert!(calls.iter().any(|x| x.stage == "egg for Blue Caves"));
+
+    let json = serde_json::to_string(&calls[0]).unwrap();
+    assert_eq!(serde_json::from_str::<TracedCall>(&json).unwrap(), calls[0]);
+
+    // Games failing to generate are traced as well, up to their last draw
+    let (game, calls) = game_manager::generate_game_traced(32410, true, true, true);
+    let draws = calls
+        .iter()
+        .filter(|x| !matches!(x.call, Call::RangeInt { min, max, .. } if min == max))
+        .count();
+
+    assert_eq!(game.err().map(|x| x.draws()), Some(draws as u64));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use std::{
+    cell::Cell,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use common::{TempDir, SEEDS};
+use sanctuary_seeder::seed_finder::{
+    backend::{self, Error},
+    verify,
+};
+
+#[test]
+fn verify() {
+    let dir = TempDir::new("verify");
+    let backend = backend::open(&dir.path().join("seeds.db")).unwrap();
+
+    common::generate(backend.as_ref());
+
+    let mismatches = verify::verify(
+        backend.as_ref(),
+        SEEDS,
+        7,
+        |_, _| {},
+        Arc::new(AtomicBool::new(false)),
+    )
+    .unwrap();
+
+    assert!(mismatches.is_empty());
+    assert_eq!(verify::sample_count(&SEEDS, 7), 286);
+    assert_eq!(verify::sample_count(&SEEDS, 2000), 1);
+
+    // Every seed is one more than a `u32` holds
+    let total = Cell::new(0);
+    let res = verify::verify(
+        backend.as_ref(),
+        0..=u32::MAX,
+        1,
+        |_, x| total.set(x),
+        Arc::new(AtomicBool::new(true)),
+    );
+
+    assert_eq!(total.get(), 1 << 32);
+    assert!(matches!(res, Err(Error::Stopped)));
+}